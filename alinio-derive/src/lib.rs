@@ -0,0 +1,119 @@
+//! Provides `#[derive(Tabular)]`, which describes the columns of a table from the fields of a
+//! struct, so that the struct can be used as the rows of an `alinio` table.
+//!
+//! This crate is re-exported by `alinio` when its `derive` feature is turned on, and shouldn't
+//! need to be used directly.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, LitStr, Path};
+
+/// Implement `alinio::column::Tabular` for a struct with named fields, with a column per field.
+///
+/// The id of each column is the name of its field, and so is its header unless it is renamed.
+/// Cells are formatted with `ToString` unless a custom display function is given.
+///
+/// Fields can be configured with the `#[tabular(...)]` attribute:
+/// - `rename = "Title"` sets the text of the header
+/// - `skip` leaves the field out of the table
+/// - `priority = 2` sets how important the column is when space is limited
+/// - `align = "right"` aligns the column to the `"left"`, `"center"` or `"right"`
+/// - `display = "path::to::function"` formats the field with a `fn(&T) -> String`
+#[proc_macro_derive(Tabular, attributes(tabular))]
+pub fn derive_tabular(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// The settings of a field, read from its `#[tabular(...)]` attributes
+#[derive(Default)]
+struct FieldSettings {
+    rename: Option<String>,
+    skip: bool,
+    priority: Option<LitInt>,
+    align: Option<TokenStream>,
+    display: Option<Path>,
+}
+
+/// Generate the implementation of `Tabular`
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    // Only structs with named fields have something to name the columns after
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                let msg = "Tabular can only be derived for structs with named fields";
+                return Err(Error::new_spanned(&input.ident, msg));
+            }
+        },
+        _ => {
+            let msg = "Tabular can only be derived for structs";
+            return Err(Error::new_spanned(&input.ident, msg));
+        }
+    };
+    // Describe a column for each field
+    let mut columns = vec![];
+    for field in fields {
+        let settings = field_settings(field)?;
+        if settings.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap();
+        let id = ident.to_string().trim_start_matches("r#").to_string();
+        let header = settings.rename.unwrap_or_else(|| id.clone());
+        let format = match settings.display {
+            Some(display) => quote!(#display(&row.#ident)),
+            None => quote!(::std::string::ToString::to_string(&row.#ident)),
+        };
+        let priority = settings.priority.map(|p| quote!(.priority(#p)));
+        let align = settings.align.map(|a| quote!(.align(#a)));
+        columns.push(quote! {
+            ::alinio::column::Column::new(#id, #header, |row: &Self| #format)
+                #priority
+                #align
+        });
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::alinio::column::Tabular for #name #ty_generics #where_clause {
+            fn columns() -> ::std::vec::Vec<::alinio::column::Column<Self>> {
+                ::std::vec![#(#columns),*]
+            }
+        }
+    })
+}
+
+/// Read the settings of a field from its `#[tabular(...)]` attributes
+fn field_settings(field: &syn::Field) -> syn::Result<FieldSettings> {
+    let mut settings = FieldSettings::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("tabular")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                settings.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                settings.skip = true;
+            } else if meta.path.is_ident("priority") {
+                settings.priority = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("align") {
+                let align: LitStr = meta.value()?.parse()?;
+                settings.align = Some(match align.value().as_str() {
+                    "left" => quote!(::alinio::table::Align::Left),
+                    "center" => quote!(::alinio::table::Align::Center),
+                    "right" => quote!(::alinio::table::Align::Right),
+                    _ => {
+                        let msg = "expected `left`, `center` or `right`";
+                        return Err(Error::new_spanned(align, msg));
+                    }
+                });
+            } else if meta.path.is_ident("display") {
+                settings.display = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else {
+                return Err(meta.error("unknown tabular attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(settings)
+}
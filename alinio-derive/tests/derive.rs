@@ -0,0 +1,82 @@
+use alinio::column::Tabular;
+use alinio::table::Table;
+
+/// Format a duration in seconds as minutes and seconds
+fn duration(seconds: &u32) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[derive(Tabular)]
+struct Track {
+    #[tabular(rename = "Title", priority = 3)]
+    title: String,
+    #[tabular(rename = "Artist")]
+    artist: String,
+    #[tabular(rename = "Length", align = "right", display = "duration", priority = 2)]
+    length: u32,
+    #[tabular(priority = 1, align = "center")]
+    year: u16,
+    #[tabular(skip)]
+    #[allow(dead_code)]
+    path: String,
+}
+
+#[derive(Tabular)]
+struct Pair<T: ToString> {
+    key: &'static str,
+    value: T,
+}
+
+fn track(title: &str, artist: &str, length: u32, year: u16) -> Track {
+    Track {
+        title: title.to_string(),
+        artist: artist.to_string(),
+        length,
+        year,
+        path: format!("/music/{}.flac", title),
+    }
+}
+
+#[test]
+fn test_derive() {
+    let tracks = vec![
+        track("Once in a Lifetime", "Talking Heads", 259, 1981),
+        track("Heroes", "David Bowie", 371, 1977),
+    ];
+    // Test the columns are described from the fields
+    let columns = Track::columns();
+    let ids: Vec<&str> = columns.iter().map(|c| c.id()).collect();
+    assert_eq!(ids, vec!["title", "artist", "length", "year"]);
+    assert_eq!(Track::headers(), vec!["Title", "Artist", "Length", "year"]);
+    assert_eq!(
+        tracks[0].cells(),
+        vec!["Once in a Lifetime", "Talking Heads", "4:19", "1981"]
+    );
+    // Test rendering, with alignment and priorities applied
+    let mut table = Table::from_rows(&tracks, 50);
+    assert_eq!(
+        table.render().unwrap(),
+        vec![
+            "Title                Artist          Length   year",
+            "Once in a Lifetime   Talking Heads     4:19   1981",
+            "Heroes               David Bowie       6:11   1977",
+        ]
+    );
+    table.set_space(30);
+    assert_eq!(
+        table.render().unwrap(),
+        vec![
+            "Title              Length year",
+            "Once in a Lifetime   4:19 1981",
+            "Heroes               6:11 1977",
+        ]
+    );
+    assert_eq!(table.column_index("length"), Some(2));
+    // Test generic structs
+    let pairs = vec![Pair { key: "a", value: 1 }, Pair { key: "b", value: 2 }];
+    let table = Table::from_rows(&pairs, 9);
+    assert_eq!(
+        table.render().unwrap(),
+        vec!["key value", "a   1    ", "b   2    "]
+    );
+}
@@ -1,16 +1,59 @@
 /// Align.rs contains everything you'll need to align and display data on the terminal.
-/// All functions in this file are compatible with unicode characters.
-use unicode_width::UnicodeWidthStr;
+/// All functions in this file are compatible with unicode characters, and ignore ANSI escape
+/// sequences (such as colours and hyperlinks) when working out how wide text is.
+use crate::error::{Error, Result};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Works out how wide the text will be when displayed on the terminal
+///
+/// ANSI escape sequences (CSI sequences such as `\x1b[31m` and OSC sequences such as hyperlinks)
+/// don't take up any space, so they are ignored.
+///
+/// Example:
+/// ```
+/// use alinio::align;
+/// assert_eq!(align::width("\x1b[1;31mhello\x1b[0m"), 5);
+/// assert_eq!(align::width("日本語"), 6);
+/// ```
+pub fn width(txt: &str) -> usize {
+    // Avoid splitting up text that has no escape sequences
+    if !txt.contains('\x1b') {
+        return txt.width();
+    }
+    tokens(txt).iter().map(|t| t.width).sum()
+}
+
+/// Removes any ANSI escape sequences from the text, leaving only the text that is displayed
+///
+/// Example:
+/// ```
+/// use alinio::align;
+/// assert_eq!(align::strip("\x1b[1;31mhello\x1b[0m"), "hello");
+/// ```
+pub fn strip(txt: &str) -> String {
+    // Avoid splitting up text that has no escape sequences
+    if !txt.contains('\x1b') {
+        return txt.to_string();
+    }
+    tokens(txt)
+        .iter()
+        .filter(|t| !t.escape)
+        .map(|t| t.text)
+        .collect()
+}
 
 /// Aligns the text to the center
 ///
-/// Will return `None` if there is not enough space to fit the text (length of txt > space)
-pub fn center(txt: &str, space: usize) -> Option<String> {
+/// Will return `Error::NotEnoughSpace` if there is not enough space to fit the text
+pub fn center(txt: &str, space: usize) -> Result<String> {
     // Determine the width of the characters when displayed
-    let len = txt.width();
-    // Return None if there is not enough space to fit the string
+    let len = width(txt);
+    // Return an error if there is not enough space to fit the string
     if len > space {
-        return None;
+        return Err(Error::NotEnoughSpace {
+            needed: len,
+            available: space,
+        });
     }
     // Work out total space needed on each side
     let left_over = space - len;
@@ -20,52 +63,58 @@ pub fn center(txt: &str, space: usize) -> Option<String> {
     // Pad the right hand side
     let right_pad = " ".repeat(left_over - each);
     // Format and return
-    Some(format!("{}{}{}", left_pad, txt, right_pad))
+    Ok(format!("{}{}{}", left_pad, txt, right_pad))
 }
 
 /// Aligns the text to the left
 ///
 /// This is particularly useful if you want to align columns in a table
 ///
-/// Will return `None` if there is not enough space to fit the text (length of txt > space)
-pub fn left(txt: &str, space: usize) -> Option<String> {
+/// Will return `Error::NotEnoughSpace` if there is not enough space to fit the text
+pub fn left(txt: &str, space: usize) -> Result<String> {
     // Determine the width of the characters when displayed
-    let len = txt.width();
-    // Return None if there is not enough space to fit the string
+    let len = width(txt);
+    // Return an error if there is not enough space to fit the string
     if len > space {
-        return None;
+        return Err(Error::NotEnoughSpace {
+            needed: len,
+            available: space,
+        });
     }
     // Work out total space needed on the right
     let left_over = space - len;
     // Pad the right hand side
     let right_pad = " ".repeat(left_over);
     // Format and return
-    Some(format!("{}{}", txt, right_pad))
+    Ok(format!("{}{}", txt, right_pad))
 }
 
 /// Aligns the text to the right
 ///
-/// Will return `None` if there is not enough space to fit the text (length of txt > space)
-pub fn right(txt: &str, space: usize) -> Option<String> {
+/// Will return `Error::NotEnoughSpace` if there is not enough space to fit the text
+pub fn right(txt: &str, space: usize) -> Result<String> {
     // Determine the width of the characters when displayed
-    let len = txt.width();
-    // Return None if there is not enough space to fit the string
+    let len = width(txt);
+    // Return an error if there is not enough space to fit the string
     if len > space {
-        return None;
+        return Err(Error::NotEnoughSpace {
+            needed: len,
+            available: space,
+        });
     }
     // Work out total space needed on the left
     let left_over = space - len;
     // Pad the right hand side
     let left_pad = " ".repeat(left_over);
     // Format and return
-    Some(format!("{}{}", left_pad, txt))
+    Ok(format!("{}{}", left_pad, txt))
 }
 
 /// Adds space between the specified strings in the `txt` slice
 ///
 /// Great for rendering a status line, or some kind of simple column set up
 ///
-/// Will return `None` if there is not enough space to fit the text (length of txt > space)
+/// Will return `Error::NotEnoughSpace` if there is not enough space to fit the text
 ///
 /// Example:
 /// ```
@@ -73,16 +122,19 @@ pub fn right(txt: &str, space: usize) -> Option<String> {
 /// let result = align::between(&["Title", "Artist", "Album"], 20); // Format 3 columns into a space of 20
 /// println!("{}", result.unwrap()); // -> "Title  Artist  Album"
 /// ```
-pub fn between(txt: &[&str], space: usize) -> Option<String> {
+pub fn between(txt: &[&str], space: usize) -> Result<String> {
     // Determine the width of the characters when displayed
-    let len: usize = txt.iter().map(|x| x.width()).sum();
-    // Return None if there is not enough space to fit the string
+    let len: usize = txt.iter().map(|x| width(x)).sum();
+    // Return an error if there is not enough space to fit the string
     if len > space {
-        return None;
+        return Err(Error::NotEnoughSpace {
+            needed: len,
+            available: space,
+        });
     }
     // Handle the case of there being < 2 columns provided
     if txt.is_empty() {
-        return Some(" ".repeat(space));
+        return Ok(" ".repeat(space));
     } else if txt.len() == 1 {
         return left(txt[0], space);
     }
@@ -105,14 +157,14 @@ pub fn between(txt: &[&str], space: usize) -> Option<String> {
     }
     // Push on final element
     result.push_str(txt.last().unwrap_or(&""));
-    Some(result)
+    Ok(result)
 }
 
 /// Adds space between the specified strings in the `txt` slice, and includes spaces on the outside
 ///
 /// Great for setting up a columns with padding on each side
 ///
-/// Will return `None` if there is not enough space to fit the text (length of txt > space)
+/// Will return `Error::NotEnoughSpace` if there is not enough space to fit the text
 ///
 /// Example:
 /// ```
@@ -120,16 +172,19 @@ pub fn between(txt: &[&str], space: usize) -> Option<String> {
 /// let result = align::around(&["Title", "Artist", "Album"], 24); // Format 3 columns into a space of 24
 /// println!("{}", result.unwrap()); // -> "  Title  Artist  Album  "
 /// ```
-pub fn around(txt: &[&str], space: usize) -> Option<String> {
+pub fn around(txt: &[&str], space: usize) -> Result<String> {
     // Determine the width of the characters when displayed
-    let len: usize = txt.iter().map(|x| x.width()).sum();
-    // Return None if there is not enough space to fit the string
+    let len: usize = txt.iter().map(|x| width(x)).sum();
+    // Return an error if there is not enough space to fit the string
     if len > space {
-        return None;
+        return Err(Error::NotEnoughSpace {
+            needed: len,
+            available: space,
+        });
     }
     // Handle the case of there being < 2 columns provided
     if txt.is_empty() {
-        return Some(" ".repeat(space));
+        return Ok(" ".repeat(space));
     } else if txt.len() == 1 {
         return center(txt[0], space);
     }
@@ -153,5 +208,315 @@ pub fn around(txt: &[&str], space: usize) -> Option<String> {
             result.push_str(col);
         }
     }
-    Some(result)
+    Ok(result)
+}
+
+/// For choosing which part of the text to cut off when truncating
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Truncate {
+    /// Cut off the end of the text, e.g. `Once in a Li…`
+    #[default]
+    End,
+    /// Cut off the start of the text, e.g. `…in a Lifetime`
+    Start,
+    /// Cut out the middle of the text, e.g. `Once i…fetime`
+    Middle,
+}
+
+/// Cuts the text down so that it fits into `space`, replacing the removed text with `ellipsis`
+///
+/// If the text has to be cut, the result will be exactly `space` wide, with a space inserted where
+/// a double-width character would have been split. Text that already fits is returned as is.
+///
+/// Example:
+/// ```
+/// use alinio::align::{self, Truncate};
+/// let result = align::truncate("Once in a Lifetime", 10, "…", Truncate::End);
+/// assert_eq!(result, "Once in a…");
+/// let result = align::truncate("/home/user/music", 9, "...", Truncate::Start);
+/// assert_eq!(result, ".../music");
+/// ```
+pub fn truncate(txt: &str, space: usize, ellipsis: &str, at: Truncate) -> String {
+    // Return the text as is if it already fits
+    if width(txt) <= space {
+        return txt.to_string();
+    }
+    // Work out how much space is left once the ellipsis is in place
+    let ellipsis = tokens(ellipsis);
+    let ellipsis = join(&ellipsis[..fit_start(&ellipsis, space)]);
+    let budget = space - width(&ellipsis);
+    // Escape sequences in the removed text are kept, so that styling carries on
+    let txt = tokens(txt);
+    let result = match at {
+        Truncate::End => {
+            let start = fit_start(&txt, budget);
+            let pad = " ".repeat(budget - sum(&txt[..start]));
+            let removed = escapes(&txt[start..]);
+            format!("{}{}{}{}", join(&txt[..start]), pad, ellipsis, removed)
+        }
+        Truncate::Start => {
+            let end = fit_end(&txt, budget);
+            let pad = " ".repeat(budget - sum(&txt[end..]));
+            let removed = escapes(&txt[..end]);
+            format!("{}{}{}{}", removed, ellipsis, pad, join(&txt[end..]))
+        }
+        Truncate::Middle => {
+            // Split the space either side of the ellipsis, favouring the start of the text
+            let start = fit_start(&txt, budget - budget / 2);
+            let end = fit_end(&txt, budget / 2).max(start);
+            let left_pad = " ".repeat(budget - budget / 2 - sum(&txt[..start]));
+            let right_pad = " ".repeat(budget / 2 - sum(&txt[end..]));
+            let removed = escapes(&txt[start..end]);
+            format!(
+                "{}{}{}{}{}{}",
+                join(&txt[..start]),
+                left_pad,
+                ellipsis,
+                removed,
+                right_pad,
+                join(&txt[end..])
+            )
+        }
+    };
+    close(result)
+}
+
+/// Aligns the text to the left, truncating it if there is not enough space
+///
+/// Unlike `left`, this always succeeds and returns text exactly `space` wide
+pub fn fit_left(txt: &str, space: usize, ellipsis: &str, at: Truncate) -> String {
+    let txt = truncate(txt, space, ellipsis, at);
+    left(&txt, space).unwrap_or(txt)
+}
+
+/// Aligns the text to the center, truncating it if there is not enough space
+///
+/// Unlike `center`, this always succeeds and returns text exactly `space` wide
+pub fn fit_center(txt: &str, space: usize, ellipsis: &str, at: Truncate) -> String {
+    let txt = truncate(txt, space, ellipsis, at);
+    center(&txt, space).unwrap_or(txt)
+}
+
+/// Aligns the text to the right, truncating it if there is not enough space
+///
+/// Unlike `right`, this always succeeds and returns text exactly `space` wide
+pub fn fit_right(txt: &str, space: usize, ellipsis: &str, at: Truncate) -> String {
+    let txt = truncate(txt, space, ellipsis, at);
+    right(&txt, space).unwrap_or(txt)
+}
+
+/// Word wraps the text so that each line fits into `space`
+///
+/// Words that are too long to fit onto a line on their own are split across lines, and any line
+/// breaks already in the text are kept. This always returns at least one line.
+/// Styling and hyperlinks that are open at the end of a line are closed, and opened again at the
+/// start of the next line, so that each line can be displayed on its own.
+///
+/// Example:
+/// ```
+/// use alinio::align;
+/// let result = align::wrap("list directory contents", 15);
+/// assert_eq!(result, vec!["list directory", "contents"]);
+/// ```
+pub fn wrap(txt: &str, space: usize) -> Vec<String> {
+    let mut result = vec![];
+    for paragraph in txt.split('\n') {
+        let start = result.len();
+        let mut line = "".to_string();
+        for word in paragraph.split_whitespace() {
+            // Add the word to the current line if it fits
+            let len = width(&line) + usize::from(!line.is_empty()) + width(word);
+            if len <= space {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                continue;
+            }
+            // Otherwise, start a new line
+            if !line.is_empty() {
+                result.push(line);
+            }
+            // Split up words that don't fit onto a line on their own
+            let mut word = &tokens(word)[..];
+            while sum(word) > space {
+                // Always take at least one character, so that this finishes
+                let mut chunk = fit_start(word, space);
+                if sum(&word[..chunk]) == 0 {
+                    chunk = word.iter().position(|t| t.width > 0).unwrap_or(0) + 1;
+                }
+                result.push(join(&word[..chunk]));
+                word = &word[chunk..];
+            }
+            line = join(word);
+        }
+        // Push the last line, unless it is empty because of a split word
+        if !line.is_empty() || result.len() == start {
+            result.push(line);
+        }
+    }
+    // Close styling at the end of each line, and carry it on at the start of the next
+    let mut carried = "".to_string();
+    result
+        .into_iter()
+        .map(|line| {
+            let line = format!("{}{}", carried, line);
+            let (style, link) = active(&line);
+            carried = format!("{}{}", style, link);
+            close(line)
+        })
+        .collect()
+}
+
+/// A single character, or an escape sequence, within some text
+struct Token<'a> {
+    /// The text that makes up this token
+    text: &'a str,
+    /// How wide this token is when displayed
+    width: usize,
+    /// Is this token an escape sequence?
+    escape: bool,
+}
+
+/// Splits text up into characters and escape sequences
+fn tokens(txt: &str) -> Vec<Token<'_>> {
+    let mut result = vec![];
+    let mut rest = txt;
+    while let Some(c) = rest.chars().next() {
+        let (len, escape) = match escape_len(rest) {
+            Some(len) => (len, true),
+            None => (c.len_utf8(), false),
+        };
+        let width = if escape { 0 } else { c.width().unwrap_or(0) };
+        result.push(Token {
+            text: &rest[..len],
+            width,
+            escape,
+        });
+        rest = &rest[len..];
+    }
+    result
+}
+
+/// Works out the length in bytes of the escape sequence at the start of the text, if there is one
+fn escape_len(txt: &str) -> Option<usize> {
+    let bytes = txt.as_bytes();
+    if bytes.first() != Some(&0x1b) {
+        return None;
+    }
+    match bytes.get(1) {
+        // CSI sequences end with a byte in the range @ to ~
+        Some(b'[') => {
+            let end = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b));
+            Some(end.map_or(bytes.len(), |e| e + 3))
+        }
+        // OSC sequences end with a BEL, or an ESC followed by a backslash
+        Some(b']') => {
+            let mut i = 2;
+            while i < bytes.len() {
+                match bytes[i] {
+                    0x07 => return Some(i + 1),
+                    0x1b if bytes.get(i + 1) == Some(&b'\\') => return Some(i + 2),
+                    _ => i += 1,
+                }
+            }
+            Some(bytes.len())
+        }
+        // Other escape sequences are any intermediate bytes followed by a single final byte
+        Some(_) => {
+            let end = bytes[1..].iter().position(|b| !(0x20..=0x2f).contains(b));
+            Some(match end {
+                Some(e) if bytes[e + 1].is_ascii() => e + 2,
+                Some(e) => e + 1,
+                None => bytes.len(),
+            })
+        }
+        None => Some(1),
+    }
+}
+
+/// Works out how many tokens from the start of the text fit into `space`
+fn fit_start(tokens: &[Token], space: usize) -> usize {
+    let mut len = 0;
+    for (i, t) in tokens.iter().enumerate() {
+        // Never split a double-width character
+        len += t.width;
+        if len > space {
+            return i;
+        }
+    }
+    tokens.len()
+}
+
+/// Works out where the tokens at the end of the text that fit into `space` start
+fn fit_end(tokens: &[Token], space: usize) -> usize {
+    let mut len = 0;
+    for (i, t) in tokens.iter().enumerate().rev() {
+        // Never split a double-width character
+        len += t.width;
+        if len > space {
+            return i + 1;
+        }
+    }
+    0
+}
+
+/// Works out how wide some tokens are when displayed
+fn sum(tokens: &[Token]) -> usize {
+    tokens.iter().map(|t| t.width).sum()
+}
+
+/// Joins tokens back together into text
+fn join(tokens: &[Token]) -> String {
+    tokens.iter().map(|t| t.text).collect()
+}
+
+/// Joins only the escape sequences within some tokens together
+fn escapes(tokens: &[Token]) -> String {
+    tokens.iter().filter(|t| t.escape).map(|t| t.text).collect()
+}
+
+/// Works out the styling and hyperlink that are left open at the end of the text, as the escape
+/// sequences that opened them
+fn active(txt: &str) -> (String, String) {
+    let mut style = "".to_string();
+    let mut link = "".to_string();
+    for t in tokens(txt).iter().filter(|t| t.escape) {
+        if let Some(params) = t
+            .text
+            .strip_prefix("\x1b[")
+            .and_then(|t| t.strip_suffix('m'))
+        {
+            // SGR sequences reset the styling when they are empty or zero
+            if params.is_empty() || params == "0" {
+                style.clear();
+            } else {
+                style.push_str(t.text);
+            }
+        } else if let Some(params) = t.text.strip_prefix("\x1b]8;") {
+            // Hyperlinks are closed by a hyperlink with no address
+            let params = params
+                .trim_end_matches(['\x07', '\\'])
+                .trim_end_matches('\x1b');
+            if params.split_once(';').map_or("", |(_, uri)| uri).is_empty() {
+                link.clear();
+            } else {
+                link = t.text.to_string();
+            }
+        }
+    }
+    (style, link)
+}
+
+/// Closes any styling or hyperlinks that are left open at the end of the text
+fn close(mut txt: String) -> String {
+    let (style, link) = active(&txt);
+    if !style.is_empty() {
+        txt.push_str("\x1b[0m");
+    }
+    if !link.is_empty() {
+        txt.push_str("\x1b]8;;\x1b\\");
+    }
+    txt
 }
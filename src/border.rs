@@ -0,0 +1,207 @@
+//! Border.rs contains the styles used to draw borders around and within tables.
+//! The unicode styles use box-drawing characters, which are all a single column wide.
+
+/// For choosing which characters to draw the borders of a table with
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderStyle {
+    /// No borders, columns are only separated with whitespace
+    #[default]
+    None,
+    /// Borders drawn with `+`, `-` and `|`
+    Ascii,
+    /// Borders drawn with light box-drawing characters, e.g. `┌─┬─┐`
+    Light,
+    /// Borders drawn with heavy box-drawing characters, e.g. `┏━┳━┓`
+    Heavy,
+    /// Borders drawn with double box-drawing characters, e.g. `╔═╦═╗`
+    Double,
+    /// Borders drawn with light box-drawing characters and rounded corners, e.g. `╭─┬─╮`
+    Rounded,
+}
+
+/// Describes which borders to draw on a table, and what style to draw them in
+///
+/// Example:
+/// ```
+/// use alinio::border::{Border, BorderStyle};
+/// use alinio::table::Table;
+/// let data = vec![
+///     vec!["Title",              "Artist"],
+///     vec!["Once in a Lifetime", "Talking Heads"],
+/// ];
+/// let mut table = Table::new(data, 40);
+/// table.set_header_rows(1);
+/// table.set_border(Border::new(BorderStyle::Rounded));
+/// println!("{}\n---", table.render().unwrap().join("\n"))
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Border {
+    /// The characters to draw the borders with
+    pub style: BorderStyle,
+    /// Draw a frame around the outside of the table?
+    pub frame: bool,
+    /// Draw a line between the header rows and the rest of the table?
+    pub header: bool,
+    /// Draw lines between each column?
+    pub columns: bool,
+}
+
+impl Border {
+    /// Create a border of the specified style, with every border turned on
+    pub const fn new(style: BorderStyle) -> Self {
+        Self {
+            style,
+            frame: true,
+            header: true,
+            columns: true,
+        }
+    }
+
+    /// Returns true if this border draws anything at all
+    pub fn is_visible(&self) -> bool {
+        self.style != BorderStyle::None
+    }
+
+    /// Work out how many characters the border takes up on each row, given a number of columns
+    pub(crate) fn padding(&self, columns: usize) -> usize {
+        if columns == 0 {
+            return 0;
+        }
+        // Each gap between columns is a separator with a space on either side
+        let gaps = (columns - 1) * 3;
+        // The frame is a line with a space on the inside, on each side
+        if self.frame {
+            gaps + 4
+        } else {
+            gaps
+        }
+    }
+
+    /// Join already aligned cells together into a row
+    pub(crate) fn row(&self, cells: &[String]) -> String {
+        let chars = self.chars();
+        let separator = if self.columns {
+            format!(" {} ", chars.vertical)
+        } else {
+            "   ".to_string()
+        };
+        let result = cells.join(&separator);
+        if self.frame {
+            format!("{} {} {}", chars.vertical, result, chars.vertical)
+        } else {
+            result
+        }
+    }
+
+    /// Draw the top line of the frame, given the width of each column
+    pub(crate) fn top(&self, widths: &[usize]) -> String {
+        let chars = self.chars();
+        self.line(widths, chars.top_left, chars.top, chars.top_right)
+    }
+
+    /// Draw the line between the header rows and the rest of the table
+    pub(crate) fn middle(&self, widths: &[usize]) -> String {
+        let chars = self.chars();
+        self.line(widths, chars.left, chars.cross, chars.right)
+    }
+
+    /// Draw the bottom line of the frame, given the width of each column
+    pub(crate) fn bottom(&self, widths: &[usize]) -> String {
+        let chars = self.chars();
+        self.line(widths, chars.bottom_left, chars.bottom, chars.bottom_right)
+    }
+
+    /// Draw a horizontal line, using the provided characters for the ends and junctions
+    fn line(&self, widths: &[usize], left: char, cross: char, right: char) -> String {
+        let chars = self.chars();
+        let cross = if self.columns {
+            cross
+        } else {
+            chars.horizontal
+        };
+        let mut result = "".to_string();
+        if self.frame {
+            result.push(left);
+        }
+        for (c, width) in widths.iter().enumerate() {
+            // Cells have a space either side, except on the outside when there is no frame
+            let mut length = *width;
+            if self.frame || c > 0 {
+                length += 1;
+            }
+            if self.frame || c + 1 < widths.len() {
+                length += 1;
+            }
+            // Push the line, with a junction before each column except the first
+            if c > 0 {
+                result.push(cross);
+            }
+            result.push_str(&chars.horizontal.to_string().repeat(length));
+        }
+        if self.frame {
+            result.push(right);
+        }
+        result
+    }
+
+    /// Get the characters that make up this border style
+    fn chars(&self) -> Chars {
+        match self.style {
+            BorderStyle::None => Chars::from([' '; 11]),
+            BorderStyle::Ascii => {
+                Chars::from(['-', '|', '+', '+', '+', '+', '+', '+', '+', '+', '+'])
+            }
+            BorderStyle::Light => {
+                Chars::from(['─', '│', '┌', '┬', '┐', '├', '┼', '┤', '└', '┴', '┘'])
+            }
+            BorderStyle::Heavy => {
+                Chars::from(['━', '┃', '┏', '┳', '┓', '┣', '╋', '┫', '┗', '┻', '┛'])
+            }
+            BorderStyle::Double => {
+                Chars::from(['═', '║', '╔', '╦', '╗', '╠', '╬', '╣', '╚', '╩', '╝'])
+            }
+            BorderStyle::Rounded => {
+                Chars::from(['─', '│', '╭', '┬', '╮', '├', '┼', '┤', '╰', '┴', '╯'])
+            }
+        }
+    }
+}
+
+/// The characters used to draw a border style
+struct Chars {
+    horizontal: char,
+    vertical: char,
+    top_left: char,
+    top: char,
+    top_right: char,
+    left: char,
+    cross: char,
+    right: char,
+    bottom_left: char,
+    bottom: char,
+    bottom_right: char,
+}
+
+impl From<[char; 11]> for Chars {
+    fn from(c: [char; 11]) -> Self {
+        Self {
+            horizontal: c[0],
+            vertical: c[1],
+            top_left: c[2],
+            top: c[3],
+            top_right: c[4],
+            left: c[5],
+            cross: c[6],
+            right: c[7],
+            bottom_left: c[8],
+            bottom: c[9],
+            bottom_right: c[10],
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Builder.rs contains a way to declare how a table should look up front, and check it against the
+//! data when the table is built.
+//! Most of the builder can be used in constants and statics, so table definitions can live
+//! alongside the rest of a user interface.
+use crate::border::{Border, BorderStyle};
+use crate::error::{Error, Result};
+use crate::style::Style;
+use crate::table::{Align, Distribution, Overflow, Ragged, Table, VerticalAlign, Width};
+
+/// Declares the configuration of a table, which can then be built with some data
+///
+/// Example:
+/// ```
+/// use alinio::builder::TableBuilder;
+/// use alinio::table::Align;
+/// static SONGS: TableBuilder = TableBuilder::new()
+///     .priorities(&[2, 0, 1])
+///     .alignment(Align::Right)
+///     .surround(true)
+///     .header_rows(1);
+///
+/// let data = vec![
+///     vec!["Title",              "Artist",        "Year"],
+///     vec!["Once in a Lifetime", "Talking Heads", "1981"],
+/// ];
+/// let table = SONGS.build(data, 25).unwrap();
+/// assert_eq!(
+///     table.render().unwrap(),
+///     vec![
+///         "              Title Year ",
+///         " Once in a Lifetime 1981 ",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableBuilder<'a> {
+    priorities: &'a [usize],
+    align: Align,
+    column_aligns: &'a [Align],
+    column_widths: &'a [Width],
+    column_overflows: &'a [Overflow],
+    column_styles: &'a [Style],
+    valign: VerticalAlign,
+    distribution: Distribution,
+    surround: bool,
+    headers: usize,
+    border: Border,
+    header_style: Style,
+    ragged: Option<Ragged>,
+}
+
+impl Default for TableBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> TableBuilder<'a> {
+    /// Create a builder for a table with the default configuration
+    pub const fn new() -> Self {
+        Self {
+            priorities: &[],
+            align: Align::Left,
+            column_aligns: &[],
+            column_widths: &[],
+            column_overflows: &[],
+            column_styles: &[],
+            valign: VerticalAlign::Top,
+            distribution: Distribution::Gutters,
+            surround: false,
+            headers: 0,
+            border: Border {
+                style: BorderStyle::None,
+                frame: false,
+                header: false,
+                columns: false,
+            },
+            header_style: Style::new(),
+            ragged: None,
+        }
+    }
+
+    /// Set the priority of every column (see `Table::set_priorities`).
+    ///
+    /// There must be a priority for each column in the table.
+    pub const fn priorities(mut self, priorities: &'a [usize]) -> Self {
+        self.priorities = priorities;
+        self
+    }
+
+    /// Set how to align each cell (see `Table::set_alignment`)
+    pub const fn alignment(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the alignment of the first columns (see `Table::set_column_alignment`).
+    ///
+    /// There can't be more alignments than there are columns in the table.
+    pub const fn column_alignments(mut self, aligns: &'a [Align]) -> Self {
+        self.column_aligns = aligns;
+        self
+    }
+
+    /// Set the width constraints of the first columns (see `Table::set_column_width`).
+    ///
+    /// There can't be more width constraints than there are columns in the table.
+    pub const fn column_widths(mut self, widths: &'a [Width]) -> Self {
+        self.column_widths = widths;
+        self
+    }
+
+    /// Set what happens to the first columns when space is limited (see
+    /// `Table::set_column_overflow`).
+    ///
+    /// There can't be more overflow behaviours than there are columns in the table.
+    pub const fn column_overflows(mut self, overflows: &'a [Overflow]) -> Self {
+        self.column_overflows = overflows;
+        self
+    }
+
+    /// Set the style of the first columns (see `Table::set_column_style`).
+    ///
+    /// There can't be more styles than there are columns in the table.
+    pub const fn column_styles(mut self, styles: &'a [Style]) -> Self {
+        self.column_styles = styles;
+        self
+    }
+
+    /// Set how to align cells in rows that span multiple lines (see
+    /// `Table::set_vertical_alignment`)
+    pub const fn vertical_alignment(mut self, valign: VerticalAlign) -> Self {
+        self.valign = valign;
+        self
+    }
+
+    /// Set where any left over space goes (see `Table::set_distribution`)
+    pub const fn distribution(mut self, distribution: Distribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Set whether padding is applied to the sides of the table (see `Table::set_surround`)
+    pub const fn surround(mut self, surround: bool) -> Self {
+        self.surround = surround;
+        self
+    }
+
+    /// Set the number of header rows (see `Table::set_header_rows`)
+    pub const fn header_rows(mut self, headers: usize) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Set the borders to draw around and within the table (see `Table::set_border`)
+    pub const fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Set the style of the header rows (see `Table::set_header_style`)
+    pub const fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Set what happens when rows have different numbers of cells (see `Table::set_ragged`).
+    ///
+    /// Unlike the other settings, this can't be used in constants and statics.
+    pub fn ragged(mut self, ragged: Ragged) -> Self {
+        self.ragged = Some(ragged);
+        self
+    }
+
+    /// Build a table with this configuration, `data` and `space`.
+    ///
+    /// This will return an error if the configuration doesn't match the shape of the data, for
+    /// example if the number of priorities doesn't match the number of columns.
+    pub fn build<T: Into<String>>(&self, data: Vec<Vec<T>>, space: usize) -> Result<Table> {
+        let mut table = Table::new(data, space);
+        if let Some(ragged) = &self.ragged {
+            table.set_ragged(ragged.clone());
+        }
+        // Check the configuration against the number of columns
+        let columns = table.column_count()?;
+        if !self.priorities.is_empty() && self.priorities.len() != columns {
+            return Err(Error::WrongLength {
+                setting: "priorities",
+                expected: columns,
+                found: self.priorities.len(),
+            });
+        }
+        let lengths = [
+            ("column alignments", self.column_aligns.len()),
+            ("column widths", self.column_widths.len()),
+            ("column overflows", self.column_overflows.len()),
+            ("column styles", self.column_styles.len()),
+        ];
+        for (setting, found) in lengths {
+            if found > columns {
+                return Err(Error::WrongLength {
+                    setting,
+                    expected: columns,
+                    found,
+                });
+            }
+        }
+        // Apply the configuration
+        table.set_priorities(self.priorities);
+        table.set_alignment(self.align);
+        for (column, align) in self.column_aligns.iter().enumerate() {
+            table.set_column_alignment(column, *align);
+        }
+        for (column, width) in self.column_widths.iter().enumerate() {
+            table.set_column_width(column, *width);
+        }
+        for (column, overflow) in self.column_overflows.iter().enumerate() {
+            table.set_column_overflow(column, *overflow);
+        }
+        for (column, style) in self.column_styles.iter().enumerate() {
+            table.set_column_style(column, *style);
+        }
+        table.set_vertical_alignment(self.valign);
+        table.set_distribution(self.distribution);
+        table.set_surround(self.surround);
+        table.set_header_rows(self.headers);
+        table.set_border(self.border);
+        table.set_header_style(self.header_style);
+        Ok(table)
+    }
+}
@@ -0,0 +1,171 @@
+//! Column.rs contains a way to describe the columns of a table up front, and then fill the table
+//! with typed rows, rather than building up rows of strings by hand.
+use crate::table::{Align, Overflow, Width};
+
+/// Derive `Tabular` for a struct, with a column for each field (requires the `derive` feature)
+#[cfg(feature = "derive")]
+pub use alinio_derive::Tabular;
+
+/// Describes a column of a table, and how to turn a row of type `R` into the text of its cell
+///
+/// Example:
+/// ```
+/// use alinio::column::Column;
+/// use alinio::table::{Align, Table};
+/// struct Song {
+///     title: &'static str,
+///     year: u16,
+/// }
+/// let columns = [
+///     Column::new("title", "Title", |s: &Song| s.title.to_string()).priority(1),
+///     Column::new("year", "Year", |s: &Song| s.year.to_string()).align(Align::Right),
+/// ];
+/// let songs = [
+///     Song { title: "Once in a Lifetime", year: 1981 },
+///     Song { title: "Heroes", year: 1977 },
+/// ];
+/// let table = Table::from_columns(&columns, &songs, 25);
+/// assert_eq!(
+///     table.render().unwrap(),
+///     vec![
+///         "Title                Year",
+///         "Once in a Lifetime   1981",
+///         "Heroes               1977",
+///     ]
+/// );
+/// ```
+pub struct Column<R> {
+    /// The name used to refer to this column
+    pub(crate) id: String,
+    /// The text of the header cell
+    pub(crate) header: String,
+    /// How important this column is when space is limited
+    pub(crate) priority: Option<usize>,
+    /// How to align the cells of this column
+    pub(crate) align: Option<Align>,
+    /// Constrains the width of this column
+    pub(crate) width: Width,
+    /// What to do with this column when space is limited
+    pub(crate) overflow: Overflow,
+    /// Turns a row into the text of its cell in this column
+    format: Box<dyn Fn(&R) -> String>,
+}
+
+impl<R> Column<R> {
+    /// Create a column with an id, the text of its header, and a function that formats a row into
+    /// the text of its cell
+    pub fn new<I, H, F>(id: I, header: H, format: F) -> Self
+    where
+        I: Into<String>,
+        H: Into<String>,
+        F: Fn(&R) -> String + 'static,
+    {
+        Self {
+            id: id.into(),
+            header: header.into(),
+            priority: None,
+            align: None,
+            width: Width::Auto,
+            overflow: Overflow::Drop,
+            format: Box::new(format),
+        }
+    }
+
+    /// Set how important this column is when space is limited (see `Table::set_priorities`).
+    ///
+    /// Columns without a priority are given a priority of 0, as long as one of the columns has a
+    /// priority.
+    pub fn priority(mut self, priority: usize) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Set how to align the cells of this column (see `Table::set_column_alignment`)
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    /// Constrain the width of this column (see `Table::set_column_width`)
+    pub fn width(mut self, width: Width) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set what happens to this column when space is limited (see `Table::set_column_overflow`)
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Get the id of this column
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the text of the header cell of this column
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Format a row into the text of its cell in this column
+    pub fn format(&self, row: &R) -> String {
+        (self.format)(row)
+    }
+}
+
+/// Format a row into the text of each of its cells, ready to be pushed onto a table
+pub fn cells<R>(columns: &[Column<R>], row: &R) -> Vec<String> {
+    columns.iter().map(|c| c.format(row)).collect()
+}
+
+/// Types that can be used as the rows of a table, by describing a column for each of their parts
+///
+/// With the `derive` feature turned on, this can be derived for structs with named fields.
+/// Example:
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use alinio::column::Tabular;
+/// use alinio::table::Table;
+/// #[derive(Tabular)]
+/// struct Track {
+///     #[tabular(rename = "Title", priority = 2)]
+///     title: String,
+///     #[tabular(rename = "Artist")]
+///     artist: String,
+///     #[tabular(rename = "Year", align = "right", priority = 1)]
+///     year: u16,
+///     #[tabular(skip)]
+///     path: String,
+/// }
+/// let tracks = vec![Track {
+///     title: "Once in a Lifetime".to_string(),
+///     artist: "Talking Heads".to_string(),
+///     year: 1981,
+///     path: "/music/once.flac".to_string(),
+/// }];
+/// let table = Table::from_rows(&tracks, 23);
+/// assert_eq!(
+///     table.render().unwrap(),
+///     vec![
+///         "Title              Year",
+///         "Once in a Lifetime 1981",
+///     ]
+/// );
+/// # }
+/// ```
+pub trait Tabular: Sized {
+    /// Describe the columns of a table with rows of this type
+    fn columns() -> Vec<Column<Self>>;
+
+    /// Get the text of the header of each column
+    fn headers() -> Vec<String> {
+        Self::columns().iter().map(|c| c.header.clone()).collect()
+    }
+
+    /// Format this row into the text of each of its cells
+    fn cells(&self) -> Vec<String> {
+        cells(&Self::columns(), self)
+    }
+}
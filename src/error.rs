@@ -0,0 +1,79 @@
+//! Error.rs contains the errors that can occur when aligning text and rendering tables.
+use std::fmt;
+
+/// Describes why text couldn't be aligned, or why a table couldn't be rendered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The text is wider than the space it has to fit into
+    NotEnoughSpace {
+        /// The width required to fit the text
+        needed: usize,
+        /// The width that was available
+        available: usize,
+    },
+    /// A row in a table has fewer cells than the table has columns
+    RaggedRow {
+        /// The index of the row
+        row: usize,
+        /// The number of columns in the table
+        expected: usize,
+        /// The number of cells in the row
+        found: usize,
+    },
+    /// A setting of a table was given for a different number of columns than the table has
+    WrongLength {
+        /// The name of the setting, e.g. `priorities`
+        setting: &'static str,
+        /// The number of columns in the table
+        expected: usize,
+        /// The number of values given for the setting
+        found: usize,
+    },
+    /// There is no column with this id
+    UnknownColumn {
+        /// The id that was looked for
+        id: String,
+    },
+    /// CSV or TSV input couldn't be read
+    Csv {
+        /// Describes what went wrong
+        message: String,
+    },
+}
+
+/// The result of aligning text or rendering a table
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotEnoughSpace { needed, available } => write!(
+                f,
+                "not enough space: {} columns needed, but only {} available",
+                needed, available
+            ),
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, but the table has {} columns",
+                row, found, expected
+            ),
+            Self::WrongLength {
+                setting,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} {} given, but the table has {} columns",
+                found, setting, expected
+            ),
+            Self::UnknownColumn { id } => write!(f, "there is no column with the id `{}`", id),
+            Self::Csv { message } => write!(f, "invalid csv: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
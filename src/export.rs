@@ -0,0 +1,287 @@
+//! Export.rs contains ways to turn tables into other formats, for use outside of the terminal.
+//! Exports contain the header rows and the rows that match the filter, and any ANSI escape
+//! sequences are removed from the cells.
+use crate::align;
+#[cfg(feature = "csv")]
+use crate::error::Error;
+use crate::error::Result;
+use crate::table::{Align, Table};
+
+/// For choosing which columns of a table are exported
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Columns {
+    /// Every column, regardless of priority
+    #[default]
+    All,
+    /// Only the columns that would be rendered if the table had this much space
+    Fit(usize),
+}
+
+impl Table {
+    /// Export this table as a GitHub flavoured Markdown table.
+    ///
+    /// The first header row becomes the header of the Markdown table, and any other header rows
+    /// are exported as normal rows. If there are no header rows, the header is left empty.
+    /// The alignment of each column is kept, pipes are escaped and new lines become `<br>`.
+    ///
+    /// This will return an error if the table is in an invalid format.
+    /// Example:
+    /// ```
+    /// use alinio::export::Columns;
+    /// use alinio::table::{Align, Table};
+    /// let data = vec![
+    ///     vec!["Title",              "Artist",        "Year"],
+    ///     vec!["Once in a Lifetime", "Talking Heads", "1981"],
+    /// ];
+    /// let mut table = Table::new(data, 40);
+    /// table.set_header_rows(1);
+    /// table.set_column_alignment(2, Align::Right);
+    /// assert_eq!(
+    ///     table.to_markdown(Columns::All).unwrap(),
+    ///     "| Title | Artist | Year |\n\
+    ///      |---|---|---:|\n\
+    ///      | Once in a Lifetime | Talking Heads | 1981 |\n"
+    /// );
+    /// ```
+    pub fn to_markdown(&self, columns: Columns) -> Result<String> {
+        let columns = self.export_columns(columns)?;
+        let mut rows = self.export_rows().into_iter();
+        let mut result = "".to_string();
+        // Markdown tables need at least one column
+        if columns.is_empty() {
+            return Ok(result);
+        }
+        // Push the header, which is empty if there are no header rows
+        let header = match self.header_rows() {
+            0 => vec!["".to_string(); columns.len()],
+            _ => self.export_cells(rows.next().unwrap_or(0), &columns, markdown),
+        };
+        result.push_str(&markdown_row(&header));
+        // Push the separator, which describes the alignment of each column
+        let separator: Vec<String> = columns
+            .iter()
+            .map(|c| match self.column_alignment(*c) {
+                Align::Left => "---",
+                Align::Center => ":---:",
+                Align::Right => "---:",
+            })
+            .map(|s| s.to_string())
+            .collect();
+        result.push_str(&format!("|{}|\n", separator.join("|")));
+        // Push the rest of the rows
+        for row in rows {
+            result.push_str(&markdown_row(&self.export_cells(row, &columns, markdown)));
+        }
+        Ok(result)
+    }
+
+    /// Export this table as an HTML table.
+    ///
+    /// Header rows are put in a `<thead>`, and the rest of the rows are put in a `<tbody>`.
+    /// The alignment of each column is set with CSS, the cell text is escaped and new lines become
+    /// `<br>`.
+    ///
+    /// This will return an error if the table is in an invalid format.
+    /// Example:
+    /// ```
+    /// use alinio::export::Columns;
+    /// use alinio::table::{Align, Table};
+    /// let data = vec![
+    ///     vec!["Title",       "Year"],
+    ///     vec!["Rock & Roll", "1970"],
+    /// ];
+    /// let mut table = Table::new(data, 40);
+    /// table.set_header_rows(1);
+    /// table.set_column_alignment(1, Align::Right);
+    /// let html = table.to_html(Columns::All).unwrap();
+    /// assert!(html.contains("<td style=\"text-align: left\">Rock &amp; Roll</td>"));
+    /// println!("{}", html);
+    /// ```
+    pub fn to_html(&self, columns: Columns) -> Result<String> {
+        let columns = self.export_columns(columns)?;
+        let rows = self.export_rows();
+        let (head, body) = rows.split_at(self.header_rows());
+        let mut result = "<table>\n".to_string();
+        // Push the header rows and the rest of the rows in their own sections
+        for (section, tag, rows) in [("thead", "th", head), ("tbody", "td", body)] {
+            if section == "thead" && rows.is_empty() {
+                continue;
+            }
+            result.push_str(&format!("  <{}>\n", section));
+            for row in rows {
+                let cells = self.export_cells(*row, &columns, html);
+                result.push_str("    <tr>");
+                for (column, cell) in columns.iter().zip(cells) {
+                    let align = match self.column_alignment(*column) {
+                        Align::Left => "left",
+                        Align::Center => "center",
+                        Align::Right => "right",
+                    };
+                    result.push_str(&format!(
+                        "<{} style=\"text-align: {}\">{}</{}>",
+                        tag, align, cell, tag
+                    ));
+                }
+                result.push_str("</tr>\n");
+            }
+            result.push_str(&format!("  </{}>\n", section));
+        }
+        result.push_str("</table>\n");
+        Ok(result)
+    }
+
+    /// Export this table as CSV, with a record for each row (requires the `csv` feature).
+    ///
+    /// Fields are quoted when they need to be.
+    ///
+    /// This will return an error if the table is in an invalid format.
+    /// Example:
+    /// ```
+    /// use alinio::export::Columns;
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Title",  "Recorded"],
+    ///     vec!["Heroes", "Berlin, 1977"],
+    /// ];
+    /// let table = Table::new(data, 40);
+    /// assert_eq!(
+    ///     table.to_csv(Columns::All).unwrap(),
+    ///     "Title,Recorded\nHeroes,\"Berlin, 1977\"\n"
+    /// );
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn to_csv(&self, columns: Columns) -> Result<String> {
+        let columns = self.export_columns(columns)?;
+        let error = |e: csv::Error| Error::Csv {
+            message: e.to_string(),
+        };
+        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(vec![]);
+        for row in self.export_rows() {
+            let cells = self.export_cells(row, &columns, str::to_string);
+            writer.write_record(&cells).map_err(error)?;
+        }
+        let result = writer
+            .into_inner()
+            .map_err(|e| error(e.into_error().into()))?;
+        Ok(String::from_utf8_lossy(&result).into_owned())
+    }
+
+    /// Export this table as a JSON array, with an object for each row after the header rows
+    /// (requires the `json` feature).
+    ///
+    /// The objects are keyed by the text of the first header row, or by the position of the
+    /// column if there are no header rows. Header cells that are empty or repeated are keyed by
+    /// the position of their column instead, so that no cells are lost. Every value is a string.
+    ///
+    /// This will return an error if the table is in an invalid format.
+    /// Example:
+    /// ```
+    /// use alinio::export::Columns;
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Title",  "Year"],
+    ///     vec!["Heroes", "1977"],
+    /// ];
+    /// let mut table = Table::new(data, 40);
+    /// table.set_header_rows(1);
+    /// assert_eq!(
+    ///     table.to_json(Columns::All).unwrap(),
+    ///     r#"[{"Title":"Heroes","Year":"1977"}]"#
+    /// );
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, columns: Columns) -> Result<String> {
+        use serde_json::{Map, Value};
+        let columns = self.export_columns(columns)?;
+        let rows = self.export_rows();
+        let (head, body) = rows.split_at(self.header_rows());
+        // Work out the keys of the objects, making sure that each of them is unique
+        let headers = head
+            .first()
+            .map(|row| self.export_cells(*row, &columns, str::to_string));
+        let mut keys: Vec<String> = vec![];
+        for (i, column) in columns.iter().enumerate() {
+            let mut key = headers.as_ref().map_or("", |h| h[i].as_str()).to_string();
+            if key.is_empty() || keys.contains(&key) {
+                key = column.to_string();
+            }
+            // Positions can still clash with header cells that look like numbers
+            let mut n = 2;
+            while keys.contains(&key) {
+                key = format!("{} ({})", column, n);
+                n += 1;
+            }
+            keys.push(key);
+        }
+        // Build up an object for each row, keeping the keys in the order of the columns
+        let objects: Vec<Value> = body
+            .iter()
+            .map(|row| {
+                let values = self.export_cells(*row, &columns, str::to_string);
+                let fields: Map<String, Value> = keys
+                    .iter()
+                    .cloned()
+                    .zip(values.into_iter().map(Value::from))
+                    .collect();
+                Value::Object(fields)
+            })
+            .collect();
+        Ok(Value::Array(objects).to_string())
+    }
+
+    /// Work out which columns to export
+    fn export_columns(&self, columns: Columns) -> Result<Vec<usize>> {
+        match columns {
+            Columns::All => Ok((0..self.column_count()?).collect()),
+            Columns::Fit(space) => Ok(self.fit_columns(&self.matches(), space)?.0),
+        }
+    }
+
+    /// Work out which rows to export, which are the header rows and the rows that match the
+    /// filter
+    fn export_rows(&self) -> Vec<usize> {
+        (0..self.header_rows()).chain(self.matches()).collect()
+    }
+
+    /// Get the cells of a row in some columns, without escape sequences, and escaped for the
+    /// format being exported to
+    fn export_cells(
+        &self,
+        row: usize,
+        columns: &[usize],
+        escape: fn(&str) -> String,
+    ) -> Vec<String> {
+        columns
+            .iter()
+            .map(|c| escape(&align::strip(self.text(row, *c))))
+            .collect()
+    }
+}
+
+/// Escape text so that it can be put in a cell of a Markdown table
+fn markdown(txt: &str) -> String {
+    txt.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Escape text so that it can be put in a cell of an HTML table
+fn html(txt: &str) -> String {
+    let mut result = "".to_string();
+    for c in txt.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            '\n' => result.push_str("<br>"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Join cells together into a row of a Markdown table
+fn markdown_row(cells: &[String]) -> String {
+    format!("| {} |\n", cells.join(" | "))
+}
@@ -0,0 +1,106 @@
+//! Import.rs contains ways to build tables from other formats, such as CSV and TSV.
+//! This requires the `csv` feature.
+use crate::error::{Error, Result};
+use crate::table::Table;
+use std::io;
+
+/// Describes how to read CSV input
+///
+/// Example:
+/// ```
+/// use alinio::import::CsvOptions;
+/// use alinio::table::Table;
+/// let input = "Name;Notes\nnotes.txt;\"shopping; todo\"\n";
+/// let options = CsvOptions::new().delimiter(b';').header(true);
+/// let table = Table::from_csv(input.as_bytes(), 30, &options).unwrap();
+/// assert_eq!(
+///     table.render().unwrap(),
+///     vec![
+///         "Name            Notes         ",
+///         "notes.txt       shopping; todo",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CsvOptions {
+    /// The character that separates fields
+    pub delimiter: u8,
+    /// The character that fields are quoted with
+    pub quote: u8,
+    /// Treat the first record as a header row?
+    pub header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvOptions {
+    /// Read comma separated fields, quoted with `"`, without a header row
+    pub const fn new() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            header: false,
+        }
+    }
+
+    /// Set the character that separates fields
+    pub const fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the character that fields are quoted with
+    pub const fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set whether the first record is treated as a header row (see `Table::set_header_rows`)
+    pub const fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+impl Table {
+    /// Create a new table from CSV input, with space.
+    ///
+    /// Quoted fields may contain delimiters, quotes (by doubling them up) and new lines.
+    /// Records don't need to have the same number of fields, see `set_ragged` for how the table
+    /// handles this.
+    ///
+    /// This will return an error if the input can't be read or isn't valid UTF-8.
+    pub fn from_csv<R: io::Read>(reader: R, space: usize, options: &CsvOptions) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(reader);
+        let mut data = vec![];
+        for record in reader.records() {
+            let record = record.map_err(|e| Error::Csv {
+                message: e.to_string(),
+            })?;
+            data.push(record.iter().map(|x| x.to_string()).collect::<Vec<_>>());
+        }
+        let mut table = Table::new(data, space);
+        if options.header {
+            table.set_header_rows(1);
+        }
+        Ok(table)
+    }
+
+    /// Create a new table from tab separated input, with space.
+    ///
+    /// This is the same as `from_csv` with a tab as the delimiter.
+    pub fn from_tsv<R: io::Read>(reader: R, space: usize, header: bool) -> Result<Self> {
+        let options = CsvOptions::new().delimiter(b'\t').header(header);
+        Self::from_csv(reader, space, &options)
+    }
+}
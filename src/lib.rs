@@ -4,10 +4,45 @@ pub mod align;
 /// Export table and column utilities
 pub mod table;
 
+/// Export border styles for tables
+pub mod border;
+
+/// Export styles for colouring text
+pub mod style;
+
+/// Export error types
+pub mod error;
+
+/// Export a builder for declaring tables
+pub mod builder;
+
+/// Export column definitions for tables of typed rows
+pub mod column;
+
+/// Export ways to sort the rows of tables
+pub mod sort;
+
+/// Export ways to turn tables into other formats
+pub mod export;
+
+/// Export ways to build tables from CSV and TSV
+#[cfg(feature = "csv")]
+pub mod import;
+
 #[cfg(test)]
 mod tests {
-    use crate::align;
-    use crate::table::{Align, Table};
+    use crate::align::{self, Truncate};
+    use crate::border::{Border, BorderStyle};
+    use crate::builder::TableBuilder;
+    use crate::column::{self, Column};
+    use crate::error::Error;
+    use crate::export::Columns;
+    use crate::sort::{Compare, Order, SortKey};
+    use crate::style::{Color, Style};
+    use crate::table::{
+        Align, Distribution, Layout, Measure, Overflow, Ragged, SelectionMode, Table,
+        VerticalAlign, Width,
+    };
     use unicode_width::UnicodeWidthStr;
 
     #[test]
@@ -15,35 +50,41 @@ mod tests {
         // Test with normal usage but odd left over space
         let out = align::center("hello, world!", 20);
         let expected = "   hello, world!    ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 20);
         // Test with normal usage but even left over space
         let out = align::center("hello, world!", 21);
         let expected = "    hello, world!    ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 21);
-        // Test returning None when there isn't enough space
+        // Test returning an error when there isn't enough space
         let out = align::center("too long!", 2);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 9,
+            available: 2,
+        });
         assert_eq!(out, expected);
         // Test behaviour with zero space
         let out = align::center("too long!", 0);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 9,
+            available: 0,
+        });
         assert_eq!(out, expected);
         // Test behaviour with empty string
         let out = align::center("", 5);
         let expected = "     ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 5);
         // Test behaviour with empty string and zero space
         let out = align::center("", 0);
         let expected = "".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 0);
         // Test behaviour with predefined space
         let out = align::center("  ", 4);
         let expected = "    ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 4);
     }
 
@@ -52,25 +93,31 @@ mod tests {
         // Test with normal usage
         let out = align::left("hello, world!", 20);
         let expected = "hello, world!       ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 20);
-        // Test returning None when there isn't enough space
+        // Test returning an error when there isn't enough space
         let out = align::left("hello, world!", 3);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 13,
+            available: 3,
+        });
         assert_eq!(out, expected);
         // Test with zero space
         let out = align::left("hello, world!", 0);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 13,
+            available: 0,
+        });
         assert_eq!(out, expected);
         // Test behaviour with empty string and zero space
         let out = align::left("", 0);
         let expected = "".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 0);
         // Test behaviour with predefined space
         let out = align::center("  ", 6);
         let expected = "      ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 6);
     }
 
@@ -79,25 +126,31 @@ mod tests {
         // Test with normal usage
         let out = align::right("hello, world!", 20);
         let expected = "       hello, world!".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 20);
-        // Test returning None when there isn't enough space
+        // Test returning an error when there isn't enough space
         let out = align::right("hello, world!", 3);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 13,
+            available: 3,
+        });
         assert_eq!(out, expected);
         // Test with zero space
         let out = align::right("hello, world!", 0);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 13,
+            available: 0,
+        });
         assert_eq!(out, expected);
         // Test behaviour with empty string and zero space
         let out = align::right("", 0);
         let expected = "".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 0);
         // Test behaviour with predefined space
         let out = align::right("  ", 6);
         let expected = "      ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 6);
     }
 
@@ -106,54 +159,63 @@ mod tests {
         // Test with 2 inputs
         let out = align::between(&["hello", "world!"], 20);
         let expected = "hello         world!".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 20);
         // Test with 3 inputs
         let out = align::between(&["NORMAL", "test.txt", "20/25"], 23);
         let expected = "NORMAL  test.txt  20/25".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 23);
         // Test with 4 inputs
         let out = align::between(&["Title", "Artist", "Album", "Year"], 25);
         let expected = "Title  Artist  Album Year".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 25);
         // Test not enough space on 2
         let out = align::between(&["hello", "world!"], 4);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 11,
+            available: 4,
+        });
         assert_eq!(out, expected);
         // Test not enough space on 3
         let out = align::between(&["NORMAL", "test.txt", "20/25"], 2);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 19,
+            available: 2,
+        });
         assert_eq!(out, expected);
         // Test not enough space on 4
         let out = align::between(&["Title", "Artist", "Album", "Year"], 10);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 20,
+            available: 10,
+        });
         assert_eq!(out, expected);
         // Test with 0 inputs
         let out = align::between(&[], 10);
         let expected = "          ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 10);
         // Test with 0 inputs and space 0
         let out = align::between(&[], 0);
         let expected = "".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 0);
         // Test with 1 input
         let out = align::between(&["yeet"], 10);
         let expected = "yeet      ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 10);
         // Test with a few empty inputs
         let out = align::between(&["", "", "", "", "", "", "", ""], 10);
         let expected = "          ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 10);
         // Test with a few predefined spaces
         let out = align::between(&[" ", "  ", "   ", " ", " ", "   ", " ", ""], 12);
         let expected = "            ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 12);
     }
 
@@ -162,54 +224,63 @@ mod tests {
         // Test with 2 inputs
         let out = align::around(&["hello", "world!"], 20);
         let expected = "   hello   world!   ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 20);
         // Test with 3 inputs
         let out = align::around(&["NORMAL", "test.txt", "20/25"], 23);
         let expected = " NORMAL test.txt 20/25 ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 23);
         // Test with 4 inputs
         let out = align::around(&["Title", "Artist", "Album", "Year"], 25);
         let expected = " Title Artist Album Year ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 25);
         // Test not enough space on 2
         let out = align::around(&["hello", "world!"], 4);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 11,
+            available: 4,
+        });
         assert_eq!(out, expected);
         // Test not enough space on 3
         let out = align::around(&["NORMAL", "test.txt", "20/25"], 2);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 19,
+            available: 2,
+        });
         assert_eq!(out, expected);
         // Test not enough space on 4
         let out = align::around(&["Title", "Artist", "Album", "Year"], 10);
-        let expected = None;
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 20,
+            available: 10,
+        });
         assert_eq!(out, expected);
         // Test with 0 inputs
         let out = align::around(&[], 10);
         let expected = "          ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 10);
         // Test with 0 inputs and space 0
         let out = align::around(&[], 0);
         let expected = "".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 0);
         // Test with 1 input
         let out = align::around(&["yeet"], 10);
         let expected = "   yeet   ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 10);
         // Test with a few empty inputs
         let out = align::around(&["", "", "", "", "", "", "", ""], 10);
         let expected = "          ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 10);
         // Test with a few predefined spaces
         let out = align::around(&[" ", "  ", "   ", " ", " ", "   ", " ", ""], 12);
         let expected = "            ".to_string();
-        assert_eq!(out, Some(expected));
+        assert_eq!(out, Ok(expected));
         assert_eq!(out.unwrap().width(), 12);
     }
 
@@ -284,12 +355,12 @@ mod tests {
         table.set_space(10);
         assert_eq!(
             table.render(),
-            Some(vec!["          ".to_string(), "          ".to_string()]),
+            Ok(vec!["          ".to_string(), "          ".to_string()]),
         );
         table.set_surround(false);
         assert_eq!(
             table.render(),
-            Some(vec!["          ".to_string(), "          ".to_string()]),
+            Ok(vec!["          ".to_string(), "          ".to_string()]),
         );
         // Test column collapsing
         table.set_surround(true);
@@ -312,22 +383,29 @@ mod tests {
         );
         // Play with empty tables
         let table = Table::new::<String>(vec![], 0);
-        assert_eq!(table.render(), Some(vec![]));
+        assert_eq!(table.render(), Ok(vec![]));
         let table = Table::new::<String>(vec![], 10);
-        assert_eq!(table.render(), Some(vec![]));
+        assert_eq!(table.render(), Ok(vec![]));
         let table = Table::new::<String>(vec![vec![], vec![]], 0);
-        assert_eq!(table.render(), Some(vec!["".to_string(), "".to_string()]));
+        assert_eq!(table.render(), Ok(vec!["".to_string(), "".to_string()]));
         let table = Table::new::<String>(vec![vec![], vec![]], 10);
         assert_eq!(
             table.render(),
-            Some(vec!["          ".to_string(), "          ".to_string()])
+            Ok(vec!["          ".to_string(), "          ".to_string()])
         );
         let table = Table::new::<String>(vec![vec![]], 0);
-        assert_eq!(table.render(), Some(vec!["".to_string()]));
+        assert_eq!(table.render(), Ok(vec!["".to_string()]));
         let table = Table::new::<String>(vec![vec![]], 1);
-        assert_eq!(table.render(), Some(vec![" ".to_string()]));
+        assert_eq!(table.render(), Ok(vec![" ".to_string()]));
         let table = Table::new(vec![vec!["".to_string()], vec![]], 100);
-        assert_eq!(table.render(), None);
+        assert_eq!(
+            table.render(),
+            Err(Error::RaggedRow {
+                row: 1,
+                expected: 1,
+                found: 0
+            })
+        );
     }
 
     #[test]
@@ -358,8 +436,8 @@ mod tests {
             table.render_partial(1).unwrap(),
             vec!["Once in a Lifetime   Talking Heads  1981".to_string()]
         );
-        assert_eq!(table.render_partial(2), Some(vec![]));
-        assert_eq!(table.render_partial(3), Some(vec![]));
+        assert_eq!(table.render_partial(2), Ok(vec![]));
+        assert_eq!(table.render_partial(3), Ok(vec![]));
         // Test context awareness
         let table = Table::new(
             vec![
@@ -381,4 +459,1631 @@ mod tests {
             vec!["Title                Artist         Year".to_string(),]
         );
     }
+
+    #[test]
+    fn test_column_alignment() {
+        let mut table = Table::new(
+            vec![
+                vec!["Name", "Kind", "Size"],
+                vec!["notes.txt", "Text", "2 KiB"],
+                vec!["movie.mp4", "Video", "700 MiB"],
+            ],
+            30,
+        );
+        // Test a single column overriding the table-wide alignment
+        table.set_column_alignment(2, Align::Right);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Name          Kind        Size".to_string(),
+                "notes.txt     Text       2 KiB".to_string(),
+                "movie.mp4     Video    700 MiB".to_string(),
+            ]
+        );
+        // Test the table-wide alignment still applies to the other columns
+        table.set_alignment(Align::Center);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "  Name        Kind        Size".to_string(),
+                "notes.txt     Text       2 KiB".to_string(),
+                "movie.mp4     Video    700 MiB".to_string(),
+            ]
+        );
+        // Test the alignment moves with its column when another column is removed
+        table.set_alignment(Align::Left);
+        table.set_priorities(&[2, 0, 1]);
+        table.set_space(17);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Name         Size".to_string(),
+                "notes.txt   2 KiB".to_string(),
+                "movie.mp4 700 MiB".to_string(),
+            ]
+        );
+        // Test resetting the column back to the table-wide alignment
+        table.reset_column_alignment(2);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Name      Size   ".to_string(),
+                "notes.txt 2 KiB  ".to_string(),
+                "movie.mp4 700 MiB".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_rows() {
+        let mut table = Table::new(
+            vec![
+                vec!["Title", "Artist"],
+                vec!["Once in a Lifetime", "Talking Heads"],
+                vec!["Heroes", "David Bowie"],
+                vec!["Roxanne", "The Police"],
+            ],
+            40,
+        );
+        table.set_header_rows(1);
+        // Test the header stays at the top while scrolling
+        assert_eq!(
+            table.render_partial(1).unwrap(),
+            vec![
+                "Title                      Artist       ".to_string(),
+                "Heroes                     David Bowie  ".to_string(),
+                "Roxanne                    The Police   ".to_string(),
+            ]
+        );
+        // Test the header is still rendered when scrolled past the end
+        assert_eq!(
+            table.render_partial(3).unwrap(),
+            vec!["Title                      Artist       ".to_string()]
+        );
+        assert_eq!(
+            table.render_partial(10).unwrap(),
+            vec!["Title                      Artist       ".to_string()]
+        );
+        // Test multiple header rows
+        table.set_header_rows(2);
+        assert_eq!(
+            table.render_partial(1).unwrap(),
+            vec![
+                "Title                      Artist       ".to_string(),
+                "Once in a Lifetime         Talking Heads".to_string(),
+                "Roxanne                    The Police   ".to_string(),
+            ]
+        );
+        // Test more header rows than there are rows
+        table.set_header_rows(10);
+        assert_eq!(table.render_partial(1).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_range_table() {
+        let mut table = Table::new(
+            vec![
+                vec!["Title", "Artist"],
+                vec!["Heroes", "David Bowie"],
+                vec!["Once in a Lifetime", "Talking Heads"],
+                vec!["Roxanne", "The Police"],
+            ],
+            34,
+        );
+        // Test rendering a window in the middle of the table
+        assert_eq!(
+            table.render_range(1, 2).unwrap(),
+            vec![
+                "Heroes               David Bowie  ".to_string(),
+                "Once in a Lifetime   Talking Heads".to_string(),
+            ]
+        );
+        // Test column widths stay stable, even if the widest rows aren't in the window
+        assert_eq!(
+            table.render_range(3, 2).unwrap(),
+            vec!["Roxanne              The Police   ".to_string()]
+        );
+        // Test header rows count towards the height
+        table.set_header_rows(1);
+        assert_eq!(
+            table.render_range(2, 2).unwrap(),
+            vec![
+                "Title                Artist       ".to_string(),
+                "Roxanne              The Police   ".to_string(),
+            ]
+        );
+        assert_eq!(
+            table.render_range(0, 1).unwrap(),
+            vec!["Title                Artist       ".to_string()]
+        );
+        // Test an empty window
+        assert_eq!(table.render_range(0, 0), Ok(vec![]));
+        // Test a window larger than the table
+        assert_eq!(table.render_range(0, 100), table.render_partial(0));
+    }
+
+    #[test]
+    fn test_border() {
+        let mut table = Table::new(
+            vec![
+                vec!["Title", "Artist", "Year"],
+                vec!["Once in a Lifetime", "Talking Heads", "1981"],
+                vec!["Heroes", "David Bowie", "1977"],
+            ],
+            50,
+        );
+        table.set_header_rows(1);
+        // Test a full border, with left over space given to the columns
+        table.set_border(Border::new(BorderStyle::Rounded));
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "╭──────────────────────┬─────────────────┬───────╮".to_string(),
+                "│ Title                │ Artist          │ Year  │".to_string(),
+                "├──────────────────────┼─────────────────┼───────┤".to_string(),
+                "│ Once in a Lifetime   │ Talking Heads   │ 1981  │".to_string(),
+                "│ Heroes               │ David Bowie     │ 1977  │".to_string(),
+                "╰──────────────────────┴─────────────────┴───────╯".to_string(),
+            ]
+        );
+        // Test without a frame
+        table.set_border(Border {
+            style: BorderStyle::Ascii,
+            frame: false,
+            header: true,
+            columns: true,
+        });
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                 | Artist           | Year   ".to_string(),
+                "----------------------+------------------+--------".to_string(),
+                "Once in a Lifetime    | Talking Heads    | 1981   ".to_string(),
+                "Heroes                | David Bowie      | 1977   ".to_string(),
+            ]
+        );
+        // Test without column separators or a header separator
+        table.set_border(Border {
+            style: BorderStyle::Double,
+            frame: true,
+            header: false,
+            columns: false,
+        });
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "╔════════════════════════════════════════════════╗".to_string(),
+                "║ Title                  Artist            Year  ║".to_string(),
+                "║ Once in a Lifetime     Talking Heads     1981  ║".to_string(),
+                "║ Heroes                 David Bowie       1977  ║".to_string(),
+                "╚════════════════════════════════════════════════╝".to_string(),
+            ]
+        );
+        // Test the border is taken into account when removing columns
+        table.set_priorities(&[2, 0, 1]);
+        table.set_border(Border::new(BorderStyle::Light));
+        table.set_space(40);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "┌──────────────────────────┬───────────┐".to_string(),
+                "│ Title                    │ Year      │".to_string(),
+                "├──────────────────────────┼───────────┤".to_string(),
+                "│ Once in a Lifetime       │ 1981      │".to_string(),
+                "│ Heroes                   │ 1977      │".to_string(),
+                "└──────────────────────────┴───────────┘".to_string(),
+            ]
+        );
+        // Test border lines count towards the height of a range
+        table.set_space(30);
+        assert_eq!(
+            table.render_range(1, 5).unwrap(),
+            vec![
+                "┌─────────────────────┬──────┐".to_string(),
+                "│ Title               │ Year │".to_string(),
+                "├─────────────────────┼──────┤".to_string(),
+                "│ Heroes              │ 1977 │".to_string(),
+                "└─────────────────────┴──────┘".to_string(),
+            ]
+        );
+        // Test the header separator is left out when not every header row fits
+        table.set_header_rows(2);
+        assert_eq!(
+            table.render_range(0, 4).unwrap(),
+            vec![
+                "┌─────────────────────┬──────┐".to_string(),
+                "│ Title               │ Year │".to_string(),
+                "└─────────────────────┴──────┘".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut table = Table::new(
+            vec![
+                vec!["Path", "Title", "Size"],
+                vec![
+                    "/home/user/music/heads.flac",
+                    "Once in a Lifetime",
+                    "24 MiB",
+                ],
+                vec!["/home/user/music/日本語.flac", "日本語のタイトル", "3 MiB"],
+            ],
+            40,
+        );
+        table.set_priorities(&[1, 2, 0]);
+        // Test truncating the start, without splitting double width characters
+        table.set_column_overflow(0, Overflow::TruncateStart);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Path           Title              Size  ".to_string(),
+                "…ic/heads.flac Once in a Lifetime 24 MiB".to_string(),
+                "…c/日本語.flac 日本語のタイトル   3 MiB ".to_string(),
+            ]
+        );
+        // Test truncating the middle, padding where a double width character was cut
+        table.set_column_overflow(0, Overflow::TruncateMiddle);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Path           Title              Size  ".to_string(),
+                "/home/u…s.flac Once in a Lifetime 24 MiB".to_string(),
+                "/home/u… .flac 日本語のタイトル   3 MiB ".to_string(),
+            ]
+        );
+        // Test the least important column is shrunk first
+        table.set_column_overflow(1, Overflow::Truncate);
+        table.set_space(30);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Path Title              Size  ".to_string(),
+                "/h…c Once in a Lifetime 24 MiB".to_string(),
+                "/h…c 日本語のタイトル   3 MiB ".to_string(),
+            ]
+        );
+        table.set_space(12);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "… Ti… Size  ".to_string(),
+                "… On… 24 MiB".to_string(),
+                "… 日… 3 MiB ".to_string(),
+            ]
+        );
+        // Test columns are only removed once everything has been shrunk
+        table.set_space(5);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "… Ti…".to_string(),
+                "… On…".to_string(),
+                "… 日…".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        // Test text that already fits is left alone
+        let out = align::truncate("hello", 10, "…", Truncate::End);
+        assert_eq!(out, "hello".to_string());
+        // Test truncating each part of the text
+        let out = align::truncate("hello, world!", 8, "…", Truncate::End);
+        assert_eq!(out, "hello, …".to_string());
+        let out = align::truncate("hello, world!", 8, "…", Truncate::Start);
+        assert_eq!(out, "… world!".to_string());
+        let out = align::truncate("hello, world!", 8, "…", Truncate::Middle);
+        assert_eq!(out, "hell…ld!".to_string());
+        // Test with a custom ellipsis
+        let out = align::truncate("hello, world!", 8, "...", Truncate::End);
+        assert_eq!(out, "hello...".to_string());
+        assert_eq!(out.width(), 8);
+        let out = align::truncate("hello, world!", 8, "", Truncate::Start);
+        assert_eq!(out, ", world!".to_string());
+        // Test an ellipsis that is wider than the space
+        let out = align::truncate("hello, world!", 2, "...", Truncate::End);
+        assert_eq!(out, "..".to_string());
+        // Test padding when a double width character would be split
+        let out = align::truncate("日本語のタイトル", 6, "…", Truncate::End);
+        assert_eq!(out, "日本 …".to_string());
+        assert_eq!(out.width(), 6);
+        let out = align::truncate("日本語のタイトル", 6, "…", Truncate::Start);
+        assert_eq!(out, "… トル".to_string());
+        assert_eq!(out.width(), 6);
+        let out = align::truncate("日本語のタイトル", 6, "…", Truncate::Middle);
+        assert_eq!(out, "日 …ル".to_string());
+        assert_eq!(out.width(), 6);
+        // Test with zero space
+        let out = align::truncate("hello, world!", 0, "…", Truncate::End);
+        assert_eq!(out, "".to_string());
+        // Test the alignment companions
+        let out = align::fit_left("hello", 8, "…", Truncate::End);
+        assert_eq!(out, "hello   ".to_string());
+        let out = align::fit_center("hello", 8, "…", Truncate::End);
+        assert_eq!(out, " hello  ".to_string());
+        let out = align::fit_right("hello", 8, "…", Truncate::End);
+        assert_eq!(out, "   hello".to_string());
+        let out = align::fit_right("hello, world!", 8, "…", Truncate::End);
+        assert_eq!(out, "hello, …".to_string());
+        let out = align::fit_left("日本語", 4, "…", Truncate::Start);
+        assert_eq!(out, "… 語".to_string());
+        assert_eq!(out.width(), 4);
+    }
+
+    #[test]
+    fn test_wrap() {
+        // Test wrapping words
+        let out = align::wrap("list directory contents", 15);
+        assert_eq!(out, vec!["list directory", "contents"]);
+        // Test splitting long words and keeping line breaks
+        let out = align::wrap("a\n\nsupercalifragilistic word", 6);
+        assert_eq!(
+            out,
+            vec!["a", "", "superc", "alifra", "gilist", "ic", "word"]
+        );
+        // Test double width characters are never split
+        let out = align::wrap("日本語のタイトル", 5);
+        assert_eq!(out, vec!["日本", "語の", "タイ", "トル"]);
+        let out = align::wrap("日本語", 1);
+        assert_eq!(out, vec!["日", "本", "語"]);
+        // Test empty text
+        let out = align::wrap("", 6);
+        assert_eq!(out, vec![""]);
+    }
+
+    #[test]
+    fn test_wrap_table() {
+        let mut table = Table::new(
+            vec![
+                vec!["Command", "Description", "Size"],
+                vec!["ls", "list directory contents", "139K"],
+                vec!["grep", "print lines that match patterns", "183K"],
+            ],
+            40,
+        );
+        table.set_header_rows(1);
+        table.set_column_wrap(1, 16);
+        table.set_column_alignment(2, Align::Right);
+        // Test wrapping to the maximum width
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Command       Description           Size".to_string(),
+                "ls            list directory        139K".to_string(),
+                "              contents                  ".to_string(),
+                "grep          print lines that      183K".to_string(),
+                "              match patterns            ".to_string(),
+            ]
+        );
+        // Test wrapped columns shrink when space is limited, and vertical alignment
+        table.set_vertical_alignment(VerticalAlign::Bottom);
+        table.set_border(Border::new(BorderStyle::Ascii));
+        table.set_space(30);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "+---------+-----------+------+".to_string(),
+                "|         | Descripti |      |".to_string(),
+                "| Command | on        | Size |".to_string(),
+                "+---------+-----------+------+".to_string(),
+                "|         | list      |      |".to_string(),
+                "|         | directory |      |".to_string(),
+                "| ls      | contents  | 139K |".to_string(),
+                "|         | print     |      |".to_string(),
+                "|         | lines     |      |".to_string(),
+                "|         | that      |      |".to_string(),
+                "|         | match     |      |".to_string(),
+                "| grep    | patterns  | 183K |".to_string(),
+                "+---------+-----------+------+".to_string(),
+            ]
+        );
+        // Test each line counts towards the height of a range
+        table.set_vertical_alignment(VerticalAlign::Middle);
+        assert_eq!(
+            table.render_range(0, 7).unwrap(),
+            vec![
+                "+---------+-----------+------+".to_string(),
+                "| Command | Descripti | Size |".to_string(),
+                "|         | on        |      |".to_string(),
+                "+---------+-----------+------+".to_string(),
+                "|         | list      |      |".to_string(),
+                "| ls      | directory | 139K |".to_string(),
+                "+---------+-----------+------+".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        // Test escape sequences don't take up any space
+        assert_eq!(align::width("\x1b[1;31mhello\x1b[0m"), 5);
+        assert_eq!(
+            align::width("\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\"),
+            4
+        );
+        assert_eq!(align::width("\x1b[38;5;10mab\x1b]0;title\x07c\x1b(B"), 3);
+        assert_eq!(align::width("\x1b["), 0);
+        // Test padding styled text
+        let out = align::left("\x1b[32mok\x1b[0m", 4);
+        assert_eq!(out, Ok("\x1b[32mok\x1b[0m  ".to_string()));
+        let out = align::between(&["\x1b[1mA\x1b[0m", "B"], 4);
+        assert_eq!(out, Ok("\x1b[1mA\x1b[0m  B".to_string()));
+        // Test truncating styled text keeps the styling
+        let out = align::truncate("\x1b[31mhello, world!\x1b[0m", 8, "…", Truncate::End);
+        assert_eq!(out, "\x1b[31mhello, …\x1b[0m".to_string());
+        let out = align::truncate("\x1b[31mhello, world!\x1b[0m", 8, "…", Truncate::Start);
+        assert_eq!(out, "\x1b[31m… world!\x1b[0m".to_string());
+        let out = align::truncate("he\x1b[4mllo, wor\x1b[0mld!", 8, "…", Truncate::Middle);
+        assert_eq!(out, "he\x1b[4mll…\x1b[0mld!".to_string());
+        // Test truncating re-closes styling and hyperlinks that were left open
+        let out = align::truncate("\x1b[31mhello, world!", 8, "…", Truncate::End);
+        assert_eq!(out, "\x1b[31mhello, …\x1b[0m".to_string());
+        let out = align::truncate("\x1b]8;;http://x\x07hello, world!", 8, "…", Truncate::End);
+        assert_eq!(
+            out,
+            "\x1b]8;;http://x\x07hello, …\x1b]8;;\x1b\\".to_string()
+        );
+        // Test wrapping styled text closes and reopens the styling on each line
+        let out = align::wrap("\x1b[31msupercalifragilistic\x1b[0m word", 6);
+        assert_eq!(
+            out,
+            vec![
+                "\x1b[31msuperc\x1b[0m",
+                "\x1b[31malifra\x1b[0m",
+                "\x1b[31mgilist\x1b[0m",
+                "\x1b[31mic\x1b[0m",
+                "word"
+            ]
+        );
+        let out = align::wrap("\x1b]8;;http://x\x07hello world\x1b]8;;\x07", 6);
+        assert_eq!(
+            out,
+            vec![
+                "\x1b]8;;http://x\x07hello\x1b]8;;\x1b\\",
+                "\x1b]8;;http://x\x07world\x1b]8;;\x07"
+            ]
+        );
+        // Test wrapped styled cells don't style their neighbours
+        let mut table = Table::new(vec![vec!["\x1b[31mhello world foo\x1b[0m", "x"]], 20);
+        table.set_column_wrap(0, 6);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "\x1b[31mhello\x1b[0m              x",
+                "\x1b[31mworld\x1b[0m               ",
+                "\x1b[31mfoo\x1b[0m                 ",
+            ]
+        );
+        // Test styled cells in tables
+        let mut table = Table::new(
+            vec![
+                vec!["Name", "Status"],
+                vec!["\x1b[1mbuild\x1b[0m", "\x1b[32mpassing\x1b[0m"],
+                vec!["docs", "\x1b[31mfailing badly\x1b[0m"],
+            ],
+            20,
+        );
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Name   Status       ".to_string(),
+                "\x1b[1mbuild\x1b[0m  \x1b[32mpassing\x1b[0m      ".to_string(),
+                "docs   \x1b[31mfailing badly\x1b[0m".to_string(),
+            ]
+        );
+        table.set_column_overflow(1, Overflow::Truncate);
+        table.set_space(12);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Name  Status".to_string(),
+                "\x1b[1mbuild\x1b[0m \x1b[32mpassi…\x1b[0m".to_string(),
+                "docs  \x1b[31mfaili…\x1b[0m".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_style() {
+        // Test rendering styles to escape sequences
+        assert_eq!(Style::new().paint("plain"), "plain".to_string());
+        let style = Style::new().fg(Color::Red).bold();
+        assert_eq!(style.paint("error"), "\x1b[1;31merror\x1b[0m".to_string());
+        let style = Style::new()
+            .fg(Color::Ansi(208))
+            .bg(Color::Rgb(0, 0, 40))
+            .italic()
+            .underline();
+        assert_eq!(style.escape(), "\x1b[3;4;38;5;208;48;2;0;0;40m".to_string());
+        let style = Style::new().bg(Color::BrightBlue).dim().reverse();
+        assert_eq!(style.escape(), "\x1b[2;7;104m".to_string());
+        // Test combining styles
+        let style = Style::new()
+            .fg(Color::Red)
+            .patch(Style::new().fg(Color::Blue).bold());
+        assert_eq!(style, Style::new().fg(Color::Blue).bold());
+        // Test styling a table
+        let mut table = Table::new(
+            vec![
+                vec!["Name", "Size"],
+                vec!["notes.txt", "2 KiB"],
+                vec!["movie.mp4", "700 MiB"],
+            ],
+            17,
+        );
+        table.set_header_rows(1);
+        table.set_header_style(Style::new().bold());
+        table.set_column_style(1, Style::new().fg(Color::Green));
+        table.set_row_style(2, Style::new().fg(Color::Red));
+        table.set_cell_style(2, 0, Style::new().underline());
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "\x1b[1mName     \x1b[0m \x1b[1;32mSize   \x1b[0m".to_string(),
+                "notes.txt \x1b[32m2 KiB  \x1b[0m".to_string(),
+                "\x1b[4;31mmovie.mp4\x1b[0m \x1b[31m700 MiB\x1b[0m".to_string(),
+            ]
+        );
+        // Test styles don't affect alignment
+        for line in table.render().unwrap() {
+            assert_eq!(align::width(&line), 17);
+        }
+        table.clear_styles();
+        assert_eq!(
+            table.render().unwrap()[2],
+            "movie.mp4 \x1b[32m700 MiB\x1b[0m".to_string()
+        );
+    }
+
+    #[test]
+    fn test_column_width() {
+        let mut table = Table::new(
+            vec![
+                vec!["#", "Title", "Artist", "Year"],
+                vec!["1", "Once in a Lifetime", "Talking Heads", "1981"],
+                vec!["2", "Heroes", "David Bowie", "1977"],
+            ],
+            50,
+        );
+        table.set_priorities(&[3, 2, 1, 0]);
+        table.set_column_width(0, Width::Min(3));
+        table.set_column_width(1, Width::Fill);
+        table.set_column_width(2, Width::Max(8));
+        table.set_column_width(3, Width::Percent(10));
+        // Test minimum, maximum, percentage and filling widths
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "#   Title                            Artist   Year".to_string(),
+                "1   Once in a Lifetime               Talking… 1981".to_string(),
+                "2   Heroes                           David B… 1977".to_string(),
+            ]
+        );
+        // Test filling and percentage columns shrink when space is limited
+        table.set_space(19);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "#   Tit… Artist   …".to_string(),
+                "1   Onc… Talking… …".to_string(),
+                "2   Her… David B… …".to_string(),
+            ]
+        );
+        // Test columns only shrink down to their minimum width
+        table.set_column_width(1, Width::Fixed(10));
+        table.set_column_width(2, Width::Min(6));
+        table.set_column_overflow(2, Overflow::Truncate);
+        table.set_space(23);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "#   Title      Artist …".to_string(),
+                "1   Once in a… Talki… …".to_string(),
+                "2   Heroes     David… …".to_string(),
+            ]
+        );
+        // Test columns are removed once every column is at its minimum width
+        table.set_space(22);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "#   Title      Artist ".to_string(),
+                "1   Once in a… Talkin…".to_string(),
+                "2   Heroes     David …".to_string(),
+            ]
+        );
+        // Test left over space only goes to columns that can grow when there is a border
+        table.set_border(Border::new(BorderStyle::Light));
+        table.set_space(40);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "┌─────┬────────────┬───────────────┬───┐".to_string(),
+                "│ #   │ Title      │ Artist        │ … │".to_string(),
+                "│ 1   │ Once in a… │ Talking Heads │ … │".to_string(),
+                "│ 2   │ Heroes     │ David Bowie   │ … │".to_string(),
+                "└─────┴────────────┴───────────────┴───┘".to_string(),
+            ]
+        );
+        // Test percentages are of the space left over after gutters and borders
+        let data = vec![vec!["Title", "Artist"], vec!["Heroes", "David Bowie"]];
+        let mut table = Table::new(data, 40);
+        table.set_column_width(0, Width::Percent(50));
+        table.set_column_width(1, Width::Percent(50));
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                Artist             ".to_string(),
+                "Heroes               David Bowie        ".to_string(),
+            ]
+        );
+        table.set_border(Border::new(BorderStyle::Light));
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "┌───────────────────┬──────────────────┐".to_string(),
+                "│ Title             │ Artist           │".to_string(),
+                "│ Heroes            │ David Bowie      │".to_string(),
+                "└───────────────────┴──────────────────┘".to_string(),
+            ]
+        );
+        table.set_column_width(0, Width::Percent(30));
+        table.set_column_width(1, Width::Percent(70));
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "┌────────────┬─────────────────────────┐".to_string(),
+                "│ Title      │ Artist                  │".to_string(),
+                "│ Heroes     │ David Bowie             │".to_string(),
+                "└────────────┴─────────────────────────┘".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distribution() {
+        let mut table = Table::new(
+            vec![
+                vec!["Title", "Artist", "Year"],
+                vec!["Once in a Lifetime", "Talking Heads", "1981"],
+            ],
+            50,
+        );
+        // Test sharing space evenly
+        table.set_distribution(Distribution::Even);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                   Artist            Year    ".to_string(),
+                "Once in a Lifetime      Talking Heads     1981    ".to_string(),
+            ]
+        );
+        // Test sharing space in proportion to the width of the columns
+        table.set_distribution(Distribution::Proportional);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                     Artist             Year ".to_string(),
+                "Once in a Lifetime        Talking Heads      1981 ".to_string(),
+            ]
+        );
+        // Test sharing space by weight
+        table.set_distribution(Distribution::Weighted);
+        table.set_column_weight(1, 3);
+        table.set_column_weight(2, 0);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                  Artist                 Year".to_string(),
+                "Once in a Lifetime     Talking Heads          1981".to_string(),
+            ]
+        );
+        // Test the gutters stay the same size with surround
+        table.set_surround(true);
+        table.set_column_alignment(2, Align::Right);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                " Title                 Artist                Year ".to_string(),
+                " Once in a Lifetime    Talking Heads         1981 ".to_string(),
+            ]
+        );
+        // Test columns that can't grow don't get any space
+        table.set_column_width(2, Width::Fixed(4));
+        table.set_distribution(Distribution::Even);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                " Title                    Artist             Year ".to_string(),
+                " Once in a Lifetime       Talking Heads      1981 ".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        // Test the error is reported when text is too long
+        let out = align::left("hello", 3);
+        let expected = Err(Error::NotEnoughSpace {
+            needed: 5,
+            available: 3,
+        });
+        assert_eq!(out, expected);
+        // Test the error messages
+        let error = Error::NotEnoughSpace {
+            needed: 5,
+            available: 3,
+        };
+        assert_eq!(
+            error.to_string(),
+            "not enough space: 5 columns needed, but only 3 available"
+        );
+        let error = Error::RaggedRow {
+            row: 2,
+            expected: 3,
+            found: 1,
+        };
+        assert_eq!(
+            error.to_string(),
+            "row 2 has 1 cells, but the table has 3 columns"
+        );
+        // Test ragged rows are reported with their position
+        let data = vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]];
+        let table = Table::new(data, 20);
+        let expected = Err(Error::RaggedRow {
+            row: 2,
+            expected: 2,
+            found: 1,
+        });
+        assert_eq!(table.render(), expected);
+    }
+
+    #[test]
+    fn test_ragged() {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Heroes", "David Bowie"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981", "extra"],
+        ];
+        // Test short rows are an error by default
+        let mut table = Table::new(data, 50);
+        let expected = Err(Error::RaggedRow {
+            row: 1,
+            expected: 3,
+            found: 2,
+        });
+        assert_eq!(table.render(), expected);
+        // Test padding short rows with a placeholder
+        table.set_ragged(Ragged::Pad("?".to_string()));
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                 Artist          Year   ?    ",
+                "Heroes                David Bowie     ?      ?    ",
+                "Once in a Lifetime    Talking Heads   1981   extra",
+            ]
+        );
+        // Test truncating long rows, keeping the columns of the first row
+        table.set_ragged(Ragged::Truncate);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                     Artist              Year",
+                "Heroes                    David Bowie             ",
+                "Once in a Lifetime        Talking Heads       1981",
+            ]
+        );
+        // Test an empty placeholder
+        let data = vec![vec!["a", "b"], vec!["c"]];
+        let mut table = Table::new(data, 5);
+        table.set_ragged(Ragged::Pad("".to_string()));
+        assert_eq!(table.render().unwrap(), vec!["a   b", "c    "]);
+    }
+
+    #[test]
+    fn test_mutation() {
+        let data = vec![vec!["Title", "Artist"], vec!["Heroes", "David Bowie"]];
+        let mut table = Table::new(data, 40);
+        // Test reading and writing cells
+        assert_eq!(table.cell(1, 0), Some("Heroes"));
+        assert_eq!(table.cell(1, 2), None);
+        assert_eq!(table.cell(2, 0), None);
+        table.set_cell(1, 0, "Heroes (2017 Remaster)");
+        assert_eq!(table.cell(1, 0), Some("Heroes (2017 Remaster)"));
+        table.set_cell(0, 3, "Genre");
+        assert_eq!(table.data()[0], vec!["Title", "Artist", "", "Genre"]);
+        assert_eq!(table.remove_column(3), vec!["Genre"]);
+        assert_eq!(table.remove_column(2), vec![""]);
+        assert_eq!(table.data()[0], vec!["Title", "Artist"]);
+        // Test adding and removing rows, with styles following their rows
+        let style = Style::new().bold();
+        table.set_row_style(1, style);
+        table.insert_row(1, vec!["Once in a Lifetime", "Talking Heads"]);
+        table.push_row(vec!["Blue Monday", "New Order"]);
+        assert_eq!(table.data().len(), 4);
+        assert_eq!(table.cell(2, 1), Some("David Bowie"));
+        let out = table.render().unwrap();
+        assert_eq!(out[1], "Once in a Lifetime         Talking Heads");
+        assert_eq!(
+            out[2],
+            "\x1b[1mHeroes (2017 Remaster)\x1b[0m     \x1b[1mDavid Bowie  \x1b[0m"
+        );
+        assert_eq!(
+            table.remove_row(2),
+            vec!["Heroes (2017 Remaster)", "David Bowie"]
+        );
+        let out = table.render().unwrap();
+        assert!(!out.iter().any(|r| r.contains('\x1b')));
+        // Test adding columns, with priorities and settings following their columns
+        table.push_column(vec!["Year", "1981", "1983"]);
+        table.set_priorities(&[2, 0, 1]);
+        table.set_column_alignment(2, Align::Right);
+        table.insert_column(0, vec!["#", "1", "2"]);
+        assert_eq!(table.data()[0], vec!["#", "Title", "Artist", "Year"]);
+        table.set_space(30);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                     Year",
+                "Once in a Lifetime        1981",
+                "Blue Monday               1983",
+            ]
+        );
+        // Test moving columns
+        table.move_column(3, 1);
+        assert_eq!(table.data()[0], vec!["#", "Year", "Title", "Artist"]);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Year        Title             ",
+                "1981        Once in a Lifetime",
+                "1983        Blue Monday       ",
+            ]
+        );
+        assert_eq!(table.remove_column(0), vec!["#", "1", "2"]);
+        table.move_column(0, 2);
+        assert_eq!(table.data()[0], vec!["Title", "Artist", "Year"]);
+        table.set_space(40);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                Artist         Year",
+                "Once in a Lifetime   Talking Heads  1981",
+                "Blue Monday          New Order      1983",
+            ]
+        );
+        // Test moving columns in tables with short rows
+        let mut ragged = Table::new(vec![vec!["a", "b", "c"], vec!["d", "e"]], 10);
+        ragged.set_ragged(Ragged::Pad("-".into()));
+        ragged.move_column(2, 0);
+        assert_eq!(ragged.data()[1], vec!["", "d", "e"]);
+        assert_eq!(ragged.render().unwrap(), vec!["c    a   b", "     d   e"]);
+        // Test pushed columns get the lowest priority
+        table.set_priorities(&[3, 2, 1]);
+        table.push_column(vec!["Genre", "New wave", "Synth-pop"]);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                Artist         Year",
+                "Once in a Lifetime   Talking Heads  1981",
+                "Blue Monday          New Order      1983",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        const SONGS: TableBuilder = TableBuilder::new()
+            .priorities(&[2, 0, 1])
+            .column_alignments(&[Align::Left, Align::Left, Align::Right])
+            .column_overflows(&[Overflow::Truncate])
+            .header_rows(1)
+            .header_style(Style::new().bold())
+            .border(Border::new(BorderStyle::Ascii));
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981"],
+        ];
+        // Test the builder configures the table the same way as the setters
+        let mut table = Table::new(data.clone(), 30);
+        table.set_priorities(&[2, 0, 1]);
+        table.set_column_alignment(2, Align::Right);
+        table.set_column_overflow(0, Overflow::Truncate);
+        table.set_header_rows(1);
+        table.set_header_style(Style::new().bold());
+        table.set_border(Border::new(BorderStyle::Ascii));
+        let built = SONGS.build(data.clone(), 30).unwrap();
+        assert_eq!(built.render(), table.render());
+        assert_eq!(
+            TableBuilder::new()
+                .build(data.clone(), 30)
+                .unwrap()
+                .render(),
+            Table::new(data.clone(), 30).render()
+        );
+        // Test the wrong number of priorities
+        let out = SONGS.priorities(&[1, 0]).build(data.clone(), 30).err();
+        let expected = Error::WrongLength {
+            setting: "priorities",
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(out, Some(expected.clone()));
+        assert_eq!(
+            expected.to_string(),
+            "2 priorities given, but the table has 3 columns"
+        );
+        // Test too many column settings
+        let widths = [Width::Auto, Width::Fill, Width::Auto, Width::Fixed(2)];
+        let out = TableBuilder::new()
+            .column_widths(&widths)
+            .build(data.clone(), 30)
+            .err();
+        let expected = Error::WrongLength {
+            setting: "column widths",
+            expected: 3,
+            found: 4,
+        };
+        assert_eq!(out, Some(expected));
+        // Test ragged rows are checked when building
+        let data = vec![vec!["Title", "Artist", "Year"], vec!["Heroes"]];
+        let out = SONGS.build(data.clone(), 30).err();
+        let expected = Error::RaggedRow {
+            row: 1,
+            expected: 3,
+            found: 1,
+        };
+        assert_eq!(out, Some(expected));
+        let table = SONGS.clone().ragged(Ragged::Pad("".to_string()));
+        assert!(table.build(data, 30).is_ok());
+    }
+
+    #[test]
+    fn test_columns() {
+        struct Song {
+            title: String,
+            artist: String,
+            year: u16,
+        }
+        let song = |title: &str, artist: &str, year| Song {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            year,
+        };
+        let columns = [
+            Column::new("title", "Title", |s: &Song| s.title.clone()).priority(2),
+            Column::new("artist", "Artist", |s: &Song| s.artist.clone()),
+            Column::new("year", "Year", |s: &Song| format!("'{}", s.year % 100))
+                .priority(1)
+                .align(Align::Right)
+                .width(Width::Min(4)),
+        ];
+        let songs = [
+            song("Once in a Lifetime", "Talking Heads", 1981),
+            song("Heroes", "David Bowie", 1977),
+        ];
+        // Test typed rows are formatted under a header row
+        let mut table = Table::from_columns(&columns, &songs, 40);
+        table.set_header_style(Style::new().bold());
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "\x1b[1mTitle             \x1b[0m   \x1b[1mArtist       \x1b[0m  \x1b[1mYear\x1b[0m",
+                "Once in a Lifetime   Talking Heads   '81",
+                "Heroes               David Bowie     '77",
+            ]
+        );
+        // Test the priorities are applied, and columns can be looked up by id
+        table.set_space(24);
+        table.set_header_style(Style::new());
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title               Year",
+                "Once in a Lifetime   '81",
+                "Heroes               '77",
+            ]
+        );
+        assert_eq!(table.column_index("year"), Some(2));
+        assert_eq!(table.column_index("album"), None);
+        // Test adding more typed rows
+        table.push_row(column::cells(
+            &columns,
+            &song("Blue Monday", "New Order", 1983),
+        ));
+        assert_eq!(table.data()[3], vec!["Blue Monday", "New Order", "'83"]);
+        // Test ids follow their columns
+        table.move_column(2, 0);
+        assert_eq!(table.column_index("year"), Some(0));
+        assert_eq!(table.column_index("title"), Some(1));
+        // Test setting priorities by id
+        table.move_column(0, 2);
+        table.set_named_priorities(&[("artist", 3)]).unwrap();
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Artist              Year",
+                "Talking Heads        '81",
+                "David Bowie          '77",
+                "New Order            '83",
+            ]
+        );
+        let out = table.set_named_priorities(&[("album", 1)]);
+        let expected = Err(Error::UnknownColumn {
+            id: "album".to_string(),
+        });
+        assert_eq!(out, expected);
+        // Test settings past the last column don't give priorities to missing columns
+        let mut table = Table::new(vec![vec!["aaaa", "zzzz"]], 9);
+        table.set_column_id(0, "a");
+        table.set_column_id(1, "b");
+        table.set_column_alignment(3, Align::Right);
+        table.set_named_priorities(&[("a", 2), ("b", 1)]).unwrap();
+        table.set_space(4);
+        assert_eq!(table.render().unwrap(), vec!["aaaa"]);
+        table.set_priorities(&[1, 2, 3, 4]);
+        assert_eq!(table.render().unwrap(), vec!["zzzz"]);
+    }
+
+    #[test]
+    fn test_sort() {
+        // Test each way of comparing cells
+        let cells = ["file10", "File3", "file2", "10", "9.5", "-1", ""];
+        let sorted = |compare: Compare| {
+            let mut cells = cells.to_vec();
+            cells.sort_by(|a, b| compare.compare(a, b));
+            cells
+        };
+        assert_eq!(
+            sorted(Compare::Lexicographic),
+            vec!["", "-1", "10", "9.5", "File3", "file10", "file2"]
+        );
+        assert_eq!(
+            sorted(Compare::Natural),
+            vec!["", "-1", "9.5", "10", "File3", "file2", "file10"]
+        );
+        assert_eq!(
+            sorted(Compare::Numeric),
+            vec!["-1", "9.5", "10", "", "File3", "file10", "file2"]
+        );
+        assert_eq!(
+            sorted(Compare::CaseInsensitive),
+            vec!["", "-1", "10", "9.5", "file10", "file2", "File3"]
+        );
+        // Test escape sequences are ignored
+        let out = Compare::Lexicographic.compare("\x1b[31mb\x1b[0m", "a");
+        assert_eq!(out, std::cmp::Ordering::Greater);
+        // Test sorting a table, keeping the header row and styles in place
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Heroes", "David Bowie", "1977"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981"],
+            vec!["Blue Monday", "New Order", "1983"],
+            vec!["Ashes to Ashes", "David Bowie", "1980"],
+        ];
+        let mut table = Table::new(data, 40);
+        table.set_header_rows(1);
+        table.set_row_style(1, Style::new().bold());
+        table.sort(&[SortKey::new(0)]);
+        let titles: Vec<&str> = table.data().iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Title",
+                "Ashes to Ashes",
+                "Blue Monday",
+                "Heroes",
+                "Once in a Lifetime"
+            ]
+        );
+        assert_eq!(
+            table.render().unwrap()[3],
+            "\x1b[1mHeroes            \x1b[0m   \x1b[1mDavid Bowie  \x1b[0m  \x1b[1m1977\x1b[0m"
+        );
+        // Test sorting by multiple keys
+        let key = SortKey {
+            column: 1,
+            order: Order::Descending,
+            compare: Compare::CaseInsensitive,
+        };
+        table.sort(&[key, SortKey::new(2).by(Compare::Numeric).descending()]);
+        let titles: Vec<&str> = table.data().iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Title",
+                "Once in a Lifetime",
+                "Blue Monday",
+                "Ashes to Ashes",
+                "Heroes"
+            ]
+        );
+        // Test sorting is stable
+        table.sort(&[SortKey::new(1)]);
+        let titles: Vec<&str> = table.data().iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Title",
+                "Ashes to Ashes",
+                "Heroes",
+                "Blue Monday",
+                "Once in a Lifetime"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_send_sync() {
+        // Test tables can be shared between threads, even with a filter
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Table>();
+        assert_send_sync::<TableBuilder>();
+    }
+
+    #[test]
+    fn test_filter() {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981"],
+            vec!["Heroes", "David Bowie", "1977"],
+            vec!["Road to Nowhere", "Talking Heads", "1985"],
+            vec!["Ashes to Ashes", "David Bowie", "1980"],
+        ];
+        let mut table = Table::new(data, 40);
+        table.set_header_rows(1);
+        // Test filtering with a predicate
+        table.set_filter(|row| row[1] == "David Bowie");
+        assert_eq!(table.matches(), vec![2, 4]);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title                Artist         Year",
+                "Heroes               David Bowie    1977",
+                "Ashes to Ashes       David Bowie    1980",
+            ]
+        );
+        // Test offsets only count matching rows
+        assert_eq!(
+            table.render_partial(1).unwrap(),
+            vec![
+                "Title                Artist         Year",
+                "Ashes to Ashes       David Bowie    1980",
+            ]
+        );
+        assert_eq!(table.render_range(0, 2).unwrap().len(), 2);
+        // Test measuring the columns with only the matching rows
+        table.set_measure(Measure::MatchingRows);
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Title               Artist          Year",
+                "Heroes              David Bowie     1977",
+                "Ashes to Ashes      David Bowie     1980",
+            ]
+        );
+        // Test searching, ignoring case, combined with the predicate
+        table.set_query("ASH");
+        assert_eq!(table.matches(), vec![4]);
+        table.clear_filter();
+        table.set_query("to");
+        assert_eq!(table.matches(), vec![3, 4]);
+        table.set_query("");
+        assert_eq!(table.matches(), vec![1, 2, 3, 4]);
+        // Test highlighting matches, returning to the style of the cell afterwards
+        table.set_query("to");
+        table.set_highlight(Style::new().fg(Color::Yellow));
+        table.set_column_style(0, Style::new().bold());
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "\x1b[1mTitle          \x1b[0m    Artist           Year",
+                "\x1b[1mRoad \x1b[33mto\x1b[0m\x1b[1m Nowhere\x1b[0m    Talking Heads    1985",
+                "\x1b[1mAshes \x1b[33mto\x1b[0m\x1b[1m Ashes \x1b[0m    David Bowie      1980",
+            ]
+        );
+        // Test nothing matching
+        table.set_query("Blondie");
+        assert_eq!(table.matches(), Vec::<usize>::new());
+        assert_eq!(table.render().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_selection() {
+        let data = vec![
+            vec!["Name", "Size"],
+            vec!["a.txt", "1 KiB"],
+            vec!["b.txt", "2 KiB"],
+            vec!["c.txt", "3 KiB"],
+            vec!["d.txt", "4 KiB"],
+        ];
+        let mut table = Table::new(data, 12);
+        table.set_header_rows(1);
+        // Test moving the cursor through the rows
+        assert_eq!(table.cursor(), None);
+        table.move_cursor(1);
+        assert_eq!(table.cursor(), Some(1));
+        table.move_cursor(2);
+        assert_eq!(table.cursor(), Some(3));
+        table.move_cursor(5);
+        assert_eq!(table.cursor(), Some(4));
+        table.move_cursor(-10);
+        assert_eq!(table.cursor(), Some(1));
+        // Test the cursor only moves through rows that match the filter
+        table.set_filter(|row| row[0] != "b.txt");
+        table.move_cursor(1);
+        assert_eq!(table.cursor(), Some(3));
+        table.clear_filter();
+        // Test single selection
+        table.select(1);
+        table.select(2);
+        assert_eq!(table.selected(), vec![2]);
+        // Test multiple selection
+        table.set_selection_mode(SelectionMode::Multi);
+        table.select(4);
+        table.toggle_selection(1);
+        assert_eq!(table.selected(), vec![1, 2, 4]);
+        table.toggle_selection(2);
+        assert!(!table.is_selected(2));
+        // Test rendering the cursor and selection across the whole line
+        table.set_cell_style(3, 1, Style::new().fg(Color::Red));
+        assert_eq!(
+            table.render().unwrap(),
+            vec![
+                "Name   Size ",
+                "\x1b[1ma.txt  1 KiB\x1b[0m",
+                "b.txt  2 KiB",
+                "\x1b[7mc.txt  \x1b[31m3 KiB\x1b[0m\x1b[7m\x1b[0m",
+                "\x1b[1md.txt  4 KiB\x1b[0m",
+            ]
+        );
+        table.set_cursor(Some(4));
+        table.set_cursor_style(Style::new().bg(Color::Blue));
+        table.set_selected_style(Style::new().fg(Color::Green));
+        assert_eq!(table.render().unwrap()[4], "\x1b[32;44md.txt  4 KiB\x1b[0m");
+        // Test the cursor and selection follow their rows
+        table.sort(&[SortKey::new(0).descending()]);
+        assert_eq!(table.cursor(), Some(1));
+        assert_eq!(table.selected(), vec![1, 4]);
+        table.remove_row(1);
+        assert_eq!(table.cursor(), None);
+        assert_eq!(table.selected(), vec![3]);
+        table.set_selection_mode(SelectionMode::Single);
+        table.clear_selection();
+        assert_eq!(table.selected(), Vec::<usize>::new());
+        // Test scrolling to keep the cursor visible
+        let mut data = vec![vec!["Name".to_string()]];
+        data.extend((0..20).map(|i| vec![format!("file{}", i)]));
+        let mut table = Table::new(data, 10);
+        table.set_header_rows(1);
+        table.set_cursor(Some(1));
+        assert_eq!(table.scroll_offset(0, 5), 0);
+        table.set_cursor(Some(5));
+        assert_eq!(table.scroll_offset(0, 5), 1);
+        assert_eq!(table.scroll_offset(2, 5), 2);
+        table.set_cursor(Some(3));
+        assert_eq!(table.scroll_offset(4, 5), 2);
+        table.set_border(Border::new(BorderStyle::Light));
+        table.set_cursor(Some(5));
+        assert_eq!(table.scroll_offset(0, 5), 4);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981"],
+            vec!["Either | Or", "Elliott Smith", "1997"],
+            vec!["\x1b[1mHeroes\x1b[0m", "David\nBowie", "1977"],
+        ];
+        let mut table = Table::new(data, 30);
+        table.set_header_rows(1);
+        table.set_priorities(&[2, 0, 1]);
+        table.set_column_alignment(1, Align::Center);
+        table.set_column_alignment(2, Align::Right);
+        // Test exporting every column, with escaping
+        assert_eq!(
+            table.to_markdown(Columns::All).unwrap(),
+            [
+                "| Title | Artist | Year |",
+                "|---|:---:|---:|",
+                "| Once in a Lifetime | Talking Heads | 1981 |",
+                "| Either \\| Or | Elliott Smith | 1997 |",
+                "| Heroes | David<br>Bowie | 1977 |",
+                "",
+            ]
+            .join("\n")
+        );
+        // Test only exporting the columns that fit, and only the rows that match the filter
+        table.set_query("heroes");
+        assert_eq!(
+            table.to_markdown(Columns::Fit(30)).unwrap(),
+            "| Title | Year |\n|---|---:|\n| Heroes | 1977 |\n"
+        );
+        // Test tables without a header row
+        table.clear_filter();
+        table.set_header_rows(0);
+        table.set_column_alignment(1, Align::Left);
+        assert_eq!(
+            table.to_markdown(Columns::Fit(20)).unwrap(),
+            [
+                "|  |",
+                "|---|",
+                "| Title |",
+                "| Once in a Lifetime |",
+                "| Either \\| Or |",
+                "| Heroes |",
+                "",
+            ]
+            .join("\n")
+        );
+        // Test tables without any columns
+        let table = Table::new::<String>(vec![], 10);
+        assert_eq!(table.to_markdown(Columns::All).unwrap(), "");
+    }
+
+    #[test]
+    fn test_html() {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["<Untitled>", "\"Weird\" Al", "1984"],
+            vec!["Heroes", "David\nBowie", "1977"],
+        ];
+        let mut table = Table::new(data, 30);
+        table.set_header_rows(1);
+        table.set_priorities(&[2, 1, 0]);
+        table.set_column_alignment(1, Align::Center);
+        // Test exporting every column, with escaping
+        let left = "style=\"text-align: left\"";
+        let center = "style=\"text-align: center\"";
+        assert_eq!(
+            table.to_html(Columns::All).unwrap(),
+            [
+                "<table>".to_string(),
+                "  <thead>".to_string(),
+                format!("    <tr><th {left}>Title</th><th {center}>Artist</th><th {left}>Year</th></tr>"),
+                "  </thead>".to_string(),
+                "  <tbody>".to_string(),
+                format!("    <tr><td {left}>&lt;Untitled&gt;</td><td {center}>&quot;Weird&quot; Al</td><td {left}>1984</td></tr>"),
+                format!("    <tr><td {left}>Heroes</td><td {center}>David<br>Bowie</td><td {left}>1977</td></tr>"),
+                "  </tbody>".to_string(),
+                "</table>".to_string(),
+                "".to_string(),
+            ]
+            .join("\n")
+        );
+        // Test only exporting the columns that fit, without header rows
+        table.set_header_rows(0);
+        table.set_query("heroes");
+        assert_eq!(
+            table.to_html(Columns::Fit(21)).unwrap(),
+            [
+                "<table>".to_string(),
+                "  <tbody>".to_string(),
+                format!("    <tr><td {left}>Heroes</td><td {center}>David<br>Bowie</td></tr>"),
+                "  </tbody>".to_string(),
+                "</table>".to_string(),
+                "".to_string(),
+            ]
+            .join("\n")
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_csv() {
+        use crate::import::CsvOptions;
+        // Test quoted fields, with delimiters, quotes and new lines inside of them
+        let input = "Title,Notes\nHeroes,\"Berlin, 1977\"\n\"Say \"\"Hi\"\"\",\"two\nlines\"\n";
+        let options = CsvOptions::new().header(true);
+        let table = Table::from_csv(input.as_bytes(), 20, &options).unwrap();
+        assert_eq!(table.header_rows(), 1);
+        assert_eq!(
+            table.data(),
+            &vec![
+                vec!["Title", "Notes"],
+                vec!["Heroes", "Berlin, 1977"],
+                vec!["Say \"Hi\"", "two\nlines"],
+            ]
+        );
+        // Test custom delimiters and quotes, and ragged records
+        let input = "a;'b;c'\nd\n";
+        let options = CsvOptions::new().delimiter(b';').quote(b'\'');
+        let mut table = Table::from_csv(input.as_bytes(), 5, &options).unwrap();
+        assert_eq!(table.header_rows(), 0);
+        assert_eq!(table.data(), &vec![vec!["a", "b;c"], vec!["d"]]);
+        table.set_ragged(Ragged::Pad("-".to_string()));
+        assert_eq!(table.render().unwrap(), vec!["a b;c", "d -  "]);
+        // Test tab separated input
+        let input = "Name\tSize\nnotes.txt\t2 KiB\n";
+        let table = Table::from_tsv(input.as_bytes(), 20, true).unwrap();
+        assert_eq!(
+            table.render().unwrap(),
+            vec!["Name           Size ", "notes.txt      2 KiB"]
+        );
+        // Test invalid input
+        let input: &[u8] = b"a,\xff\n";
+        let out = Table::from_csv(input, 20, &CsvOptions::new());
+        assert!(matches!(out, Err(Error::Csv { .. })));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_csv_export() {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Heroes", "David Bowie", "1977"],
+            vec!["Say \"Hi\"", "The\nBand", "2001"],
+            vec!["\x1b[1mBlue Monday\x1b[0m", "New Order", "1983"],
+        ];
+        let mut table = Table::new(data, 20);
+        table.set_header_rows(1);
+        table.set_priorities(&[2, 0, 1]);
+        // Test exporting every column, with quoting
+        assert_eq!(
+            table.to_csv(Columns::All).unwrap(),
+            [
+                "Title,Artist,Year",
+                "Heroes,David Bowie,1977",
+                "\"Say \"\"Hi\"\"\",\"The\nBand\",2001",
+                "Blue Monday,New Order,1983",
+                "",
+            ]
+            .join("\n")
+        );
+        // Test only exporting the columns that fit, and the rows that match the filter
+        table.set_filter(|row| row[2] != "2001");
+        assert_eq!(
+            table.to_csv(Columns::Fit(20)).unwrap(),
+            "Title,Year\nHeroes,1977\nBlue Monday,1983\n"
+        );
+        // Test the exported data can be imported again
+        let csv = table.to_csv(Columns::All).unwrap();
+        let options = crate::import::CsvOptions::new().header(true);
+        let imported = Table::from_csv(csv.as_bytes(), 20, &options).unwrap();
+        assert_eq!(imported.data().len(), 3);
+        assert_eq!(imported.cell(2, 0), Some("Blue Monday"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_export() {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Heroes", "David Bowie", "1977"],
+            vec!["Say \"Hi\"", "The\nBand", "2001"],
+        ];
+        let mut table = Table::new(data, 20);
+        table.set_header_rows(1);
+        table.set_priorities(&[2, 0, 1]);
+        // Test objects are keyed by the header, in the order of the columns
+        assert_eq!(
+            table.to_json(Columns::All).unwrap(),
+            concat!(
+                r#"[{"Title":"Heroes","Artist":"David Bowie","Year":"1977"},"#,
+                r#"{"Title":"Say \"Hi\"","Artist":"The\nBand","Year":"2001"}]"#
+            )
+        );
+        // Test only exporting the columns that fit, and the rows that match the filter
+        table.set_query("bowie");
+        assert_eq!(
+            table.to_json(Columns::Fit(15)).unwrap(),
+            r#"[{"Title":"Heroes","Year":"1977"}]"#
+        );
+        // Test extra header rows and tables without header rows
+        table.clear_filter();
+        table.set_header_rows(2);
+        assert_eq!(
+            table.to_json(Columns::All).unwrap(),
+            r#"[{"Title":"Say \"Hi\"","Artist":"The\nBand","Year":"2001"}]"#
+        );
+        table.set_header_rows(0);
+        let json = table.to_json(Columns::All).unwrap();
+        assert!(json.starts_with(r#"[{"0":"Title","1":"Artist","2":"Year"},"#));
+        // Test empty tables
+        let table = Table::new::<String>(vec![], 20);
+        assert_eq!(table.to_json(Columns::All).unwrap(), "[]");
+        // Test empty and repeated header cells are keyed by the position of their column
+        let data = vec![vec!["a", "a", "", "1"], vec!["1", "2", "3", "4"]];
+        let mut table = Table::new(data, 20);
+        table.set_header_rows(1);
+        assert_eq!(
+            table.to_json(Columns::All).unwrap(),
+            r#"[{"a":"1","1":"2","2":"3","3":"4"}]"#
+        );
+        table.set_cell(0, 2, "1");
+        assert_eq!(
+            table.to_json(Columns::All).unwrap(),
+            r#"[{"a":"1","1":"2","2":"3","3":"4"}]"#
+        );
+        table.set_cell(0, 3, "3");
+        table.set_cell(0, 2, "");
+        table.set_cell(0, 1, "2");
+        assert_eq!(
+            table.to_json(Columns::All).unwrap(),
+            r#"[{"a":"1","2":"2","2 (2)":"3","3":"4"}]"#
+        );
+    }
+
+    /// Build a table with a customised layout, for testing layouts
+    fn layout_table() -> Table {
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Heroes", "David Bowie", "1977"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981"],
+        ];
+        let mut table = Table::new(data, 40);
+        table.set_priorities(&[2, 0, 1]);
+        table.set_column_id(2, "year");
+        table.set_column_alignment(2, Align::Right);
+        table.set_column_width(1, Width::Max(8));
+        table.set_column_style(0, Style::new().fg(Color::Red));
+        table.set_header_rows(1);
+        table.set_header_style(Style::new().bold());
+        table.set_border(Border::new(BorderStyle::Ascii));
+        table.set_distribution(Distribution::Even);
+        table
+    }
+
+    #[test]
+    fn test_layout() {
+        let table = layout_table();
+        let layout = table.layout();
+        assert_eq!(layout.priorities, vec![2, 0, 1]);
+        assert_eq!(layout.columns[2].id.as_deref(), Some("year"));
+        assert_eq!(layout.columns[1].width, Width::Max(8));
+        // Test the layout can be copied over to a table with different data
+        let data = vec![
+            vec!["Title", "Artist", "Year"],
+            vec!["Heroes", "David Bowie", "1977"],
+            vec!["Once in a Lifetime", "Talking Heads", "1981"],
+        ];
+        let mut other = Table::new(data, 40);
+        assert_ne!(other.render().unwrap(), table.render().unwrap());
+        other.set_layout(layout.clone());
+        assert_eq!(other.layout(), layout);
+        assert_eq!(other.render().unwrap(), table.render().unwrap());
+        assert_eq!(other.column_index("year"), Some(2));
+        // Test the default layout is the layout of a new table
+        other.set_layout(Layout::default());
+        assert_eq!(other.layout(), Table::new(vec![vec!["a"]], 0).layout());
+        assert_eq!(
+            Table::default().layout(),
+            Table::new::<String>(vec![], 0).layout()
+        );
+        assert_eq!(
+            Table::default().layout().cursor_style,
+            Style::new().reverse()
+        );
+        assert_eq!(other.header_rows(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let table = layout_table();
+        let layout = table.layout();
+        // Test the layout survives a round trip through JSON
+        let json = serde_json::to_string(&layout).unwrap();
+        let restored: Layout = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, layout);
+        let mut other = layout_table();
+        other.set_layout(Layout::default());
+        other.set_layout(restored);
+        assert_eq!(other.render().unwrap(), table.render().unwrap());
+        // Test settings that are left out fall back to their defaults
+        let restored: Layout = serde_json::from_str(r#"{"priorities":[0,1,2]}"#).unwrap();
+        assert_eq!(restored.priorities, vec![0, 1, 2]);
+        assert_eq!(restored.border, Border::default());
+        assert_eq!(restored.align, Align::Left);
+        // Test other settings can be serialized too
+        let key = SortKey::new(1).by(Compare::Natural).descending();
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(serde_json::from_str::<SortKey>(&json).unwrap(), key);
+        let json = serde_json::to_string(&Columns::Fit(20)).unwrap();
+        assert_eq!(json, r#"{"Fit":20}"#);
+    }
 }
@@ -0,0 +1,182 @@
+//! Sort.rs contains the ways that the rows of a table can be ordered when sorting them.
+//! Cells are compared by the text that is displayed, so ANSI escape sequences are ignored.
+use crate::align;
+use std::cmp::Ordering;
+
+/// For choosing which way round rows are sorted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Order {
+    /// Smallest first, e.g. `a, b, c`
+    #[default]
+    Ascending,
+    /// Largest first, e.g. `c, b, a`
+    Descending,
+}
+
+/// For choosing how two cells are compared
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compare {
+    /// Compare the characters of the text, e.g. `B < a` and `file10 < file2`
+    #[default]
+    Lexicographic,
+    /// Compare numbers within the text by their value, e.g. `file2 < file10`
+    Natural,
+    /// Compare the text as numbers, e.g. `9.5 < 10`, with text that isn't a number last
+    Numeric,
+    /// Compare the characters of the text, ignoring case, e.g. `a < B`
+    CaseInsensitive,
+}
+
+impl Compare {
+    /// Compare two cells
+    ///
+    /// Example:
+    /// ```
+    /// use alinio::sort::Compare;
+    /// use std::cmp::Ordering;
+    /// assert_eq!(Compare::Lexicographic.compare("file10", "file2"), Ordering::Less);
+    /// assert_eq!(Compare::Natural.compare("file10", "file2"), Ordering::Greater);
+    /// ```
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let (a, b) = (align::strip(a), align::strip(b));
+        let ordering = match self {
+            Self::Lexicographic => Ordering::Equal,
+            Self::Natural => natural(&a, &b),
+            Self::Numeric => numeric(&a, &b),
+            Self::CaseInsensitive => a.to_lowercase().cmp(&b.to_lowercase()),
+        };
+        // Fall back to comparing the characters, so that different text is never equal
+        ordering.then_with(|| a.cmp(&b))
+    }
+}
+
+/// Describes a column to sort the rows of a table by
+///
+/// Example:
+/// ```
+/// use alinio::sort::{Compare, SortKey};
+/// use alinio::table::Table;
+/// let data = vec![
+///     vec!["Name",      "Size"],
+///     vec!["file10.txt", "2"],
+///     vec!["file2.txt",  "10"],
+///     vec!["file1.txt",  "10"],
+/// ];
+/// let mut table = Table::new(data, 20);
+/// table.set_header_rows(1);
+/// // Sort by size, largest first, and then by name
+/// table.sort(&[
+///     SortKey::new(1).by(Compare::Numeric).descending(),
+///     SortKey::new(0).by(Compare::Natural),
+/// ]);
+/// assert_eq!(
+///     table.render().unwrap(),
+///     vec![
+///         "Name            Size",
+///         "file1.txt       10  ",
+///         "file2.txt       10  ",
+///         "file10.txt      2   ",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SortKey {
+    /// The column to sort by
+    pub column: usize,
+    /// Which way round to sort
+    pub order: Order,
+    /// How to compare the cells of the column
+    pub compare: Compare,
+}
+
+impl SortKey {
+    /// Sort by a column in ascending order, comparing the cells lexicographically
+    pub const fn new(column: usize) -> Self {
+        Self {
+            column,
+            order: Order::Ascending,
+            compare: Compare::Lexicographic,
+        }
+    }
+
+    /// Sort in descending order
+    pub const fn descending(mut self) -> Self {
+        self.order = Order::Descending;
+        self
+    }
+
+    /// Set how the cells of the column are compared
+    pub const fn by(mut self, compare: Compare) -> Self {
+        self.compare = compare;
+        self
+    }
+
+    /// Compare two rows by the cell in this column, missing cells are treated as empty
+    pub(crate) fn compare_rows(&self, a: &[String], b: &[String]) -> Ordering {
+        let a = a.get(self.column).map_or("", |c| c.as_str());
+        let b = b.get(self.column).map_or("", |c| c.as_str());
+        let ordering = self.compare.compare(a, b);
+        match self.order {
+            Order::Ascending => ordering,
+            Order::Descending => ordering.reverse(),
+        }
+    }
+}
+
+/// Compare text, treating runs of digits as numbers
+fn natural(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (chunks(a), chunks(b));
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if is_digits(x) && is_digits(y) => {
+                // Numbers with more digits are bigger, once leading zeros are ignored
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            }
+            (Some(x), Some(y)) => x.cmp(y),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+/// Compare text as numbers, putting text that isn't a number after any numbers
+fn numeric(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.total_cmp(&y),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    }
+}
+
+/// Split text up into runs of digits and runs of everything else
+fn chunks(txt: &str) -> impl Iterator<Item = &str> {
+    let mut rest = txt;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let digit = first.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digit)
+            .unwrap_or(rest.len());
+        let (chunk, after) = rest.split_at(end);
+        rest = after;
+        Some(chunk)
+    })
+}
+
+/// Returns true if the text is made up of digits
+fn is_digits(txt: &str) -> bool {
+    txt.bytes().all(|b| b.is_ascii_digit())
+}
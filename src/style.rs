@@ -0,0 +1,198 @@
+//! Style.rs contains a way to describe the colours and attributes of text, which can then be
+//! rendered to ANSI escape sequences.
+//! Escape sequences take up no space on the terminal, so styled text still aligns correctly.
+
+/// For setting the foreground or background colour of text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A colour from the 256 colour palette
+    Ansi(u8),
+    /// A 24-bit colour
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Work out the SGR parameters for this colour, `base` is 30 for foreground and 40 for background
+    fn params(&self, base: u8) -> String {
+        match self {
+            Self::Black => format!("{}", base),
+            Self::Red => format!("{}", base + 1),
+            Self::Green => format!("{}", base + 2),
+            Self::Yellow => format!("{}", base + 3),
+            Self::Blue => format!("{}", base + 4),
+            Self::Magenta => format!("{}", base + 5),
+            Self::Cyan => format!("{}", base + 6),
+            Self::White => format!("{}", base + 7),
+            Self::BrightBlack => format!("{}", base + 60),
+            Self::BrightRed => format!("{}", base + 61),
+            Self::BrightGreen => format!("{}", base + 62),
+            Self::BrightYellow => format!("{}", base + 63),
+            Self::BrightBlue => format!("{}", base + 64),
+            Self::BrightMagenta => format!("{}", base + 65),
+            Self::BrightCyan => format!("{}", base + 66),
+            Self::BrightWhite => format!("{}", base + 67),
+            Self::Ansi(n) => format!("{};5;{}", base + 8, n),
+            Self::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+}
+
+/// Describes the colours and attributes of some text
+///
+/// Example:
+/// ```
+/// use alinio::style::{Color, Style};
+/// let style = Style::new().fg(Color::Red).bold();
+/// assert_eq!(style.paint("error"), "\x1b[1;31merror\x1b[0m");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Style {
+    /// The colour of the text
+    pub fg: Option<Color>,
+    /// The colour behind the text
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// Swap the foreground and background colours
+    pub reverse: bool,
+}
+
+impl Style {
+    /// Create a style that doesn't change the text at all
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+            reverse: false,
+        }
+    }
+
+    /// Set the colour of the text
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Set the colour behind the text
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Make the text bold
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Make the text dim
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Make the text italic
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Underline the text
+    pub const fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Swap the foreground and background colours
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Returns true if this style doesn't change the text at all
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Combine two styles, where anything set in `other` takes precedence over this style
+    pub fn patch(self, other: Style) -> Self {
+        Self {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underline: self.underline || other.underline,
+            reverse: self.reverse || other.reverse,
+        }
+    }
+
+    /// Render this style to the ANSI escape sequence that turns it on
+    ///
+    /// This is empty if the style is plain
+    pub fn escape(&self) -> String {
+        let mut params = vec![];
+        // Push attributes
+        let attributes = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.reverse, "7"),
+        ];
+        for (on, param) in attributes {
+            if on {
+                params.push(param.to_string());
+            }
+        }
+        // Push colours
+        if let Some(fg) = self.fg {
+            params.push(fg.params(30));
+        }
+        if let Some(bg) = self.bg {
+            params.push(bg.params(40));
+        }
+        if params.is_empty() {
+            return "".to_string();
+        }
+        format!("\x1b[{}m", params.join(";"))
+    }
+
+    /// Apply this style to some text, resetting the style afterwards
+    ///
+    /// The text is returned as is if the style is plain
+    pub fn paint(&self, txt: &str) -> String {
+        if self.is_plain() {
+            return txt.to_string();
+        }
+        format!("{}{}\x1b[0m", self.escape(), txt)
+    }
+}
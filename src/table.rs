@@ -1,26 +1,222 @@
 /// Table.rs contains a high level way to render and display tables in crossterm.
 /// This of course is all compatible with unicode characters.
-use crate::align;
-use unicode_width::UnicodeWidthStr;
+use crate::align::{self, Truncate};
+use crate::border::Border;
+use crate::column::{self, Column, Tabular};
+use crate::error::{Error, Result};
+use crate::sort::SortKey;
+use crate::style::Style;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+/// The text used to show where a cell has been cut off
+const ELLIPSIS: &str = "…";
 
 /// Represents the data in a table
 pub type Data = Vec<Vec<String>>;
 
+/// Decides whether a row should be rendered, given the cells of the row
+type Filter = Box<dyn Fn(&[String]) -> bool + Send + Sync>;
+
 /// Find the longest string in a list of strings
+///
+/// ANSI escape sequences are ignored, as they don't take up any space.
 pub fn find_longest(column: &[&String]) -> usize {
-    column.iter().map(|i| i.width()).max().unwrap_or(0)
+    column.iter().map(|i| align::width(i)).max().unwrap_or(0)
 }
 
 /// For setting the alignment of cells within the table
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
+    #[default]
     Left,
     Center,
     Right,
 }
 
-impl Default for Align {
+/// For setting the vertical alignment of cells in rows that span multiple lines
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// For choosing what happens to a column when there isn't enough space to fit the table
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /// Remove the whole column, e.g. `Once in a Lifetime` disappears
+    #[default]
+    Drop,
+    /// Cut off the end of the cells, e.g. `Once in a Li…`
+    Truncate,
+    /// Cut off the start of the cells, e.g. `…in a Lifetime` (useful for file paths)
+    TruncateStart,
+    /// Cut out the middle of the cells, e.g. `Once i…fetime`
+    TruncateMiddle,
+}
+
+/// For constraining the width of a column
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Width {
+    /// As wide as the widest cell in the column
+    #[default]
+    Auto,
+    /// Exactly this wide, cutting off cells that are wider
+    Fixed(usize),
+    /// At least this wide, and can be shrunk down to this width when space is limited
+    Min(usize),
+    /// At most this wide, cutting off cells that are wider
+    Max(usize),
+    /// A percentage of the space of the table, after any gutters and borders, and can be shrunk
+    /// down to a single character when space is limited
+    Percent(usize),
+    /// Takes up any space left over once the other columns fit, and can be shrunk down to a single
+    /// character when space is limited
+    Fill,
+}
+
+/// For choosing where any space left over once every column fits goes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Distribution {
+    /// Put the space in the gutters between the columns
+    #[default]
+    Gutters,
+    /// Share the space evenly between the columns
+    Even,
+    /// Share the space between the columns, in proportion to how wide they are
+    Proportional,
+    /// Share the space between the columns, in proportion to their weights
+    Weighted,
+}
+
+/// For choosing how many rows can be selected at once
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelectionMode {
+    /// Selecting a row deselects any other row
+    #[default]
+    Single,
+    /// Any number of rows can be selected
+    Multi,
+}
+
+/// For choosing which rows the widths of the columns are worked out from, when the table is
+/// filtered
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Measure {
+    /// Use every row, so the layout stays the same as the filter changes
+    #[default]
+    AllRows,
+    /// Use only the header rows and the rows that match the filter
+    MatchingRows,
+}
+
+/// For choosing what happens when rows in the table have different numbers of cells
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Ragged {
+    /// The first row decides how many columns there are, and rendering fails with
+    /// `Error::RaggedRow` if any row has fewer cells than that
+    #[default]
+    Error,
+    /// The longest row decides how many columns there are, and missing cells are filled in with
+    /// this placeholder text
+    Pad(String),
+    /// The first row decides how many columns there are, any extra cells are ignored and missing
+    /// cells are left empty
+    Truncate,
+}
+
+/// Settings that apply to a single column of the table
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ColumnSettings {
+    /// The name used to refer to this column
+    pub id: Option<String>,
+    /// Overrides the table-wide alignment for this column
+    pub align: Option<Align>,
+    /// What to do with this column when space is limited
+    pub overflow: Overflow,
+    /// The maximum width of this column, when its cells are word wrapped
+    pub wrap: Option<usize>,
+    /// The style of the cells in this column
+    pub style: Style,
+    /// Constrains the width of this column
+    pub width: Width,
+    /// How much of the left over space this column gets, compared to other columns
+    pub weight: Option<usize>,
+}
+
+/// The configuration of a table, without its data, such as its priorities, alignment and borders
+///
+/// With the `serde` feature turned on, this can be saved to and restored from a config file.
+/// Example:
+/// ```
+/// use alinio::table::{Align, Table};
+/// let data = vec![vec!["Title", "Year"], vec!["Heroes", "1977"]];
+/// let mut table = Table::new(data.clone(), 20);
+/// table.set_priorities(&[1, 0]);
+/// table.set_column_alignment(1, Align::Right);
+/// // Copy the configuration over to another table
+/// let layout = table.layout();
+/// let mut other = Table::new(data, 20);
+/// other.set_layout(layout);
+/// assert_eq!(other.render(), table.render());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Layout {
+    /// The priority of each column (see `Table::set_priorities`)
+    pub priorities: Vec<usize>,
+    /// How to align each cell (see `Table::set_alignment`)
+    pub align: Align,
+    /// How to align cells in rows that span multiple lines (see `Table::set_vertical_alignment`)
+    pub valign: VerticalAlign,
+    /// Where any left over space goes (see `Table::set_distribution`)
+    pub distribution: Distribution,
+    /// The settings of each column
+    pub columns: Vec<ColumnSettings>,
+    /// Surround with padding? (see `Table::set_surround`)
+    pub surround: bool,
+    /// The number of header rows (see `Table::set_header_rows`)
+    pub headers: usize,
+    /// The borders to draw around and within the table (see `Table::set_border`)
+    pub border: Border,
+    /// The style of the header rows (see `Table::set_header_style`)
+    pub header_style: Style,
+    /// What to do with rows that have a different number of cells (see `Table::set_ragged`)
+    pub ragged: Ragged,
+    /// The style of the parts of cells that match the query (see `Table::set_highlight`)
+    pub highlight: Style,
+    /// Which rows the widths of the columns are worked out from (see `Table::set_measure`)
+    pub measure: Measure,
+    /// How many rows can be selected at once (see `Table::set_selection_mode`)
+    pub selection_mode: SelectionMode,
+    /// The style of the row that the cursor is on (see `Table::set_cursor_style`)
+    pub cursor_style: Style,
+    /// The style of the rows that are selected (see `Table::set_selected_style`)
+    pub selected_style: Style,
+}
+
+impl Default for Layout {
     fn default() -> Self {
-        Self::Left
+        Table::default().layout()
     }
 }
 
@@ -37,18 +233,84 @@ impl Default for Align {
 /// println!("{}\n---", table.render().unwrap().join("\n"))
 /// ```
 /// This will print a table
-#[derive(Default)]
 pub struct Table {
     /// Stores the data within this table
     data: Data,
     /// Stores the priority of each column
     priorities: Vec<usize>,
-    /// How to align each cell
+    /// How to align each cell (unless overridden by the column)
     align: Align,
+    /// How to align cells in rows that span multiple lines
+    valign: VerticalAlign,
+    /// Where any left over space goes
+    distribution: Distribution,
+    /// Stores the settings of each column
+    columns: Vec<ColumnSettings>,
     /// The space that this table has
     space: usize,
     /// Surround with padding?
     surround: bool,
+    /// The number of rows at the top of the table that are headers
+    headers: usize,
+    /// The borders to draw around and within the table
+    border: Border,
+    /// The style of the header rows
+    header_style: Style,
+    /// Stores the style of individual rows
+    row_styles: HashMap<usize, Style>,
+    /// Stores the style of individual cells, by row and then column
+    cell_styles: HashMap<(usize, usize), Style>,
+    /// What to do with rows that have a different number of cells
+    ragged: Ragged,
+    /// Only rows that this returns true for are rendered
+    filter: Option<Filter>,
+    /// Only rows with a cell containing this text are rendered
+    query: Option<String>,
+    /// The style of the parts of cells that match the query
+    highlight: Style,
+    /// Which rows the widths of the columns are worked out from
+    measure: Measure,
+    /// The row that the cursor is on
+    cursor: Option<usize>,
+    /// The rows that are selected
+    selected: BTreeSet<usize>,
+    /// How many rows can be selected at once
+    selection_mode: SelectionMode,
+    /// The style of the row that the cursor is on
+    cursor_style: Style,
+    /// The style of the rows that are selected
+    selected_style: Style,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self {
+            data: vec![],
+            priorities: vec![],
+            align: Align::default(),
+            valign: VerticalAlign::default(),
+            distribution: Distribution::default(),
+            columns: vec![],
+            space: 0,
+            surround: false,
+            headers: 0,
+            border: Border::default(),
+            header_style: Style::default(),
+            row_styles: HashMap::new(),
+            cell_styles: HashMap::new(),
+            ragged: Ragged::default(),
+            filter: None,
+            query: None,
+            highlight: Style::default(),
+            measure: Measure::default(),
+            cursor: None,
+            selected: BTreeSet::new(),
+            selection_mode: SelectionMode::default(),
+            // Make the cursor and the selection visible
+            cursor_style: Style::new().reverse(),
+            selected_style: Style::new().bold(),
+        }
+    }
 }
 
 impl Table {
@@ -74,6 +336,485 @@ impl Table {
         table
     }
 
+    /// Create a new table from column definitions and typed rows.
+    ///
+    /// The first row of the table is a header row made up of the headers of the columns, and the
+    /// id, priority, alignment, width and overflow of each column are applied to the table.
+    /// See `Column` for an example.
+    pub fn from_columns<R>(columns: &[Column<R>], rows: &[R], space: usize) -> Self {
+        let mut data = vec![columns.iter().map(|c| c.header.clone()).collect()];
+        data.extend(rows.iter().map(|row| column::cells(columns, row)));
+        let mut table = Table::new(data, space);
+        table.set_header_rows(1);
+        // Only set the priorities if any of the columns have one
+        if columns.iter().any(|c| c.priority.is_some()) {
+            table.priorities = columns.iter().map(|c| c.priority.unwrap_or(0)).collect();
+        }
+        for (c, column) in columns.iter().enumerate() {
+            let settings = table.column_mut(c);
+            settings.id = Some(column.id.clone());
+            settings.align = column.align;
+            settings.width = column.width;
+            settings.overflow = column.overflow;
+        }
+        table
+    }
+
+    /// Create a new table from typed rows, using the columns that the type describes.
+    ///
+    /// This is the same as `from_columns` with the columns from `Tabular::columns`.
+    pub fn from_rows<R: Tabular>(rows: &[R], space: usize) -> Self {
+        Self::from_columns(&R::columns(), rows, space)
+    }
+
+    /// Get the data within this table, organized into rows and then columns
+    pub fn data(&self) -> &Data {
+        &self.data
+    }
+
+    /// Get the text of a cell, or `None` if the cell doesn't exist
+    pub fn cell(&self, row: usize, column: usize) -> Option<&str> {
+        self.data.get(row)?.get(column).map(|x| x.as_str())
+    }
+
+    /// Replace the text of a cell.
+    ///
+    /// If the row is shorter than `column`, it is extended with empty cells.
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn set_cell<T: Into<String>>(&mut self, row: usize, column: usize, text: T) {
+        let row = &mut self.data[row];
+        if row.len() <= column {
+            row.resize(column + 1, "".to_string());
+        }
+        row[column] = text.into();
+    }
+
+    /// Add a row to the bottom of the table
+    pub fn push_row<T: Into<String>>(&mut self, row: Vec<T>) {
+        self.data.push(row.into_iter().map(|x| x.into()).collect());
+    }
+
+    /// Insert a row at position `index`, moving every row after it down.
+    ///
+    /// Row and cell styles, the cursor and the selection move with their rows. The number of
+    /// header rows doesn't change.
+    ///
+    /// Panics if `index` is greater than the number of rows.
+    pub fn insert_row<T: Into<String>>(&mut self, index: usize, row: Vec<T>) {
+        self.data
+            .insert(index, row.into_iter().map(|x| x.into()).collect());
+        self.remap_rows(|r| Some(if r >= index { r + 1 } else { r }));
+    }
+
+    /// Remove the row at position `index` and return it, moving every row after it up.
+    ///
+    /// Row and cell styles, the cursor and the selection move with their rows, and those of the
+    /// removed row are discarded.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_row(&mut self, index: usize) -> Vec<String> {
+        let row = self.data.remove(index);
+        self.remap_rows(|r| match r.cmp(&index) {
+            Ordering::Less => Some(r),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(r - 1),
+        });
+        row
+    }
+
+    /// Add a column to the right of the table, with one cell for each row.
+    ///
+    /// Rows without a cell provided are given an empty cell.
+    pub fn push_column<T: Into<String>>(&mut self, cells: Vec<T>) {
+        let index = self.data.iter().map(|row| row.len()).max().unwrap_or(0);
+        self.insert_column(index, cells);
+    }
+
+    /// Insert a column at position `index`, with one cell for each row, moving every column
+    /// after it to the right.
+    ///
+    /// Rows without a cell provided are given an empty cell, and rows shorter than `index` are
+    /// extended with empty cells.
+    /// Column settings, priorities and cell styles move with their columns. The new column has
+    /// default settings and a priority of 0.
+    pub fn insert_column<T: Into<String>>(&mut self, index: usize, cells: Vec<T>) {
+        let mut cells = cells.into_iter().map(|x| x.into());
+        for row in &mut self.data {
+            if row.len() < index {
+                row.resize(index, "".to_string());
+            }
+            row.insert(index, cells.next().unwrap_or_default());
+        }
+        // Keep the settings and priorities in line with the columns
+        if index < self.columns.len() {
+            self.columns.insert(index, ColumnSettings::default());
+        }
+        if !self.priorities.is_empty() {
+            if self.priorities.len() < index {
+                self.priorities.resize(index, 0);
+            }
+            self.priorities.insert(index, 0);
+        }
+        self.remap_columns(|c| Some(if c >= index { c + 1 } else { c }));
+    }
+
+    /// Remove the column at position `index` and return its cells, moving every column after it
+    /// to the left.
+    ///
+    /// Rows that are too short to have a cell in this column are left as they are.
+    /// Column settings, priorities and cell styles move with their columns, and those of the
+    /// removed column are discarded.
+    pub fn remove_column(&mut self, index: usize) -> Vec<String> {
+        let mut cells = vec![];
+        for row in &mut self.data {
+            if index < row.len() {
+                cells.push(row.remove(index));
+            }
+        }
+        // Keep the settings and priorities in line with the columns
+        if index < self.columns.len() {
+            self.columns.remove(index);
+        }
+        if index < self.priorities.len() {
+            self.priorities.remove(index);
+        }
+        self.remap_columns(|c| match c.cmp(&index) {
+            Ordering::Less => Some(c),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(c - 1),
+        });
+        cells
+    }
+
+    /// Move the column at position `from` so that it ends up at position `to`, shifting the
+    /// columns in between.
+    ///
+    /// Rows that are too short to have a cell in either position are extended with empty cells.
+    /// Column settings, priorities and cell styles move with their columns.
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Title",              "Artist",        "Year"],
+    ///     vec!["Once in a Lifetime", "Talking Heads", "1981"],
+    /// ];
+    /// let mut table = Table::new(data, 40);
+    /// table.move_column(2, 0);
+    /// assert_eq!(table.data()[0], vec!["Year", "Title", "Artist"]);
+    /// ```
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        for row in &mut self.data {
+            if row.len() <= from.max(to) {
+                row.resize(from.max(to) + 1, "".to_string());
+            }
+            let cell = row.remove(from);
+            row.insert(to, cell);
+        }
+        // Keep the settings and priorities in line with the columns
+        if from.min(to) < self.columns.len() {
+            self.column_mut(from.max(to));
+            let settings = self.columns.remove(from);
+            self.columns.insert(to, settings);
+        }
+        if !self.priorities.is_empty() {
+            if self.priorities.len() <= from.max(to) {
+                self.priorities.resize(from.max(to) + 1, 0);
+            }
+            let priority = self.priorities.remove(from);
+            self.priorities.insert(to, priority);
+        }
+        self.remap_columns(|c| Some(moved(c, from, to)));
+    }
+
+    /// Sort the rows of the table by one or more columns, in order of importance.
+    ///
+    /// Header rows (see `set_header_rows`) stay at the top of the table, and rows that are equal
+    /// stay in the same order. Row and cell styles, the cursor and the selection move with their
+    /// rows.
+    /// See `SortKey` for an example.
+    pub fn sort(&mut self, keys: &[SortKey]) {
+        let headers = self.headers.min(self.data.len());
+        // Work out the new order of the rows
+        let mut order: Vec<usize> = (headers..self.data.len()).collect();
+        order.sort_by(|a, b| {
+            keys.iter()
+                .map(|k| k.compare_rows(&self.data[*a], &self.data[*b]))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        // Move the rows into their new positions
+        let mut position: Vec<usize> = (0..self.data.len()).collect();
+        for (new, old) in order.iter().enumerate() {
+            position[*old] = headers + new;
+        }
+        let mut rows = std::mem::take(&mut self.data);
+        self.data = (0..headers)
+            .chain(order)
+            .map(|r| std::mem::take(&mut rows[r]))
+            .collect();
+        self.remap_rows(|r| position.get(r).copied());
+    }
+
+    /// Only render the rows that `filter` returns true for, given the cells of the row.
+    ///
+    /// Header rows (see `set_header_rows`) are always rendered, and `offset` in `render_partial`
+    /// and `render_range` counts only the rows that match.
+    /// The filter has to be `Send` and `Sync`, so that the table can be shared between threads.
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Title",              "Year"],
+    ///     vec!["Once in a Lifetime", "1981"],
+    ///     vec!["Heroes",             "1977"],
+    /// ];
+    /// let mut table = Table::new(data, 24);
+    /// table.set_header_rows(1);
+    /// table.set_filter(|row| row[1] < "1980".to_string());
+    /// assert_eq!(
+    ///     table.render().unwrap(),
+    ///     vec![
+    ///         "Title               Year",
+    ///         "Heroes              1977",
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_filter<F: Fn(&[String]) -> bool + Send + Sync + 'static>(&mut self, filter: F) {
+        self.filter = Some(Box::new(filter));
+    }
+
+    /// Only render the rows with a cell that contains `query`, ignoring case.
+    ///
+    /// This can be combined with `set_filter`, in which case rows must match both. An empty
+    /// query matches every row.
+    pub fn set_query(&mut self, query: &str) {
+        self.query = Some(query.to_string()).filter(|q| !q.is_empty());
+    }
+
+    /// Remove the filter and the query, so that every row is rendered again.
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+        self.query = None;
+    }
+
+    /// Set the style of the parts of cells that match the query (see `set_query`).
+    ///
+    /// Matches are only highlighted in cells that aren't already styled with escape sequences,
+    /// and matches that are cut off or split across lines aren't highlighted.
+    /// Example:
+    /// ```
+    /// use alinio::style::Style;
+    /// use alinio::table::Table;
+    /// let data = vec![vec!["Talking Heads"], vec!["David Bowie"]];
+    /// let mut table = Table::new(data, 13);
+    /// table.set_query("head");
+    /// table.set_highlight(Style::new().reverse());
+    /// assert_eq!(table.render().unwrap(), vec!["Talking \x1b[7mHead\x1b[0ms"]);
+    /// ```
+    pub fn set_highlight(&mut self, style: Style) {
+        self.highlight = style;
+    }
+
+    /// Choose which rows the widths of the columns are worked out from when the table is filtered.
+    ///
+    /// By default, every row is used, so that the layout doesn't change while a filter is
+    /// being typed.
+    pub fn set_measure(&mut self, measure: Measure) {
+        self.measure = measure;
+    }
+
+    /// Get the number of header rows (see `set_header_rows`)
+    pub fn header_rows(&self) -> usize {
+        self.headers.min(self.data.len())
+    }
+
+    /// Get the positions of the rows after the header rows that match the filter and the query
+    pub fn matches(&self) -> Vec<usize> {
+        let headers = self.headers.min(self.data.len());
+        (headers..self.data.len())
+            .filter(|r| self.is_match(&self.data[*r]))
+            .collect()
+    }
+
+    /// Put the cursor on a row, or remove the cursor with `None`.
+    ///
+    /// The row that the cursor is on is drawn in the cursor style (see `set_cursor_style`), which
+    /// covers the whole line, including the gaps between the cells.
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let data = vec![vec!["a", "b"], vec!["c", "d"]];
+    /// let mut table = Table::new(data, 4);
+    /// table.set_cursor(Some(1));
+    /// assert_eq!(table.render().unwrap(), vec!["a  b", "\x1b[7mc  d\x1b[0m"]);
+    /// ```
+    pub fn set_cursor(&mut self, row: Option<usize>) {
+        self.cursor = row;
+    }
+
+    /// Get the row that the cursor is on
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Move the cursor up (when `delta` is negative) or down through the rows that match the
+    /// filter (see `set_filter`), stopping at the first and last rows.
+    ///
+    /// If the cursor isn't on a matching row, it is put on the first matching row.
+    pub fn move_cursor(&mut self, delta: isize) {
+        let rows = self.matches();
+        let position = self.cursor.and_then(|c| rows.iter().position(|r| *r == c));
+        self.cursor = match position {
+            Some(p) => rows.get(p.saturating_add_signed(delta).min(rows.len() - 1)),
+            None => rows.first(),
+        }
+        .copied();
+    }
+
+    /// Choose how many rows can be selected at once.
+    ///
+    /// Switching to single selection keeps only the last of the selected rows.
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) {
+        self.selection_mode = mode;
+        if mode == SelectionMode::Single {
+            let last = self.selected.pop_last();
+            self.selected.extend(last);
+        }
+    }
+
+    /// Select a row, deselecting any other row if only a single row can be selected
+    pub fn select(&mut self, row: usize) {
+        if self.selection_mode == SelectionMode::Single {
+            self.selected.clear();
+        }
+        self.selected.insert(row);
+    }
+
+    /// Deselect a row
+    pub fn deselect(&mut self, row: usize) {
+        self.selected.remove(&row);
+    }
+
+    /// Select a row if it isn't selected, otherwise deselect it
+    pub fn toggle_selection(&mut self, row: usize) {
+        if self.is_selected(row) {
+            self.deselect(row);
+        } else {
+            self.select(row);
+        }
+    }
+
+    /// Deselect every row
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Returns true if a row is selected
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected.contains(&row)
+    }
+
+    /// Get the rows that are selected, in order
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// Set the style of the row that the cursor is on, which is reverse video by default
+    pub fn set_cursor_style(&mut self, style: Style) {
+        self.cursor_style = style;
+    }
+
+    /// Set the style of the rows that are selected, which is bold by default
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    /// Work out the offset to render from (see `render_range`) so that the cursor is visible in a
+    /// viewport of `height` lines, moving the current `offset` as little as possible.
+    ///
+    /// Header rows and border lines are taken into account, but rows are assumed to take up a
+    /// single line each.
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let mut data = vec![vec!["Name".to_string()]];
+    /// for i in 0..100 {
+    ///     data.push(vec![format!("file{}.txt", i)]);
+    /// }
+    /// let mut table = Table::new(data, 20);
+    /// table.set_header_rows(1);
+    /// // Show the header and 9 rows, with the cursor at the bottom
+    /// table.set_cursor(Some(50));
+    /// let offset = table.scroll_offset(0, 10);
+    /// assert_eq!(offset, 41);
+    /// let view = table.render_range(offset, 10).unwrap();
+    /// assert_eq!(view[9], "\x1b[7mfile49.txt          \x1b[0m");
+    /// ```
+    pub fn scroll_offset(&self, offset: usize, height: usize) -> usize {
+        let rows = self.matches();
+        let position = match self.cursor.and_then(|c| rows.iter().position(|r| *r == c)) {
+            Some(position) => position,
+            None => return offset,
+        };
+        // Work out how many rows fit in the viewport
+        let border = self.border.is_visible();
+        let frame = border && self.border.frame;
+        let separator = border && self.border.header && self.headers > 0;
+        let height = height.saturating_sub(2 * frame as usize + separator as usize);
+        let visible = height
+            .saturating_sub(self.headers.min(self.data.len()))
+            .max(1);
+        // Scroll as little as possible
+        if position < offset {
+            position
+        } else if position >= offset + visible {
+            position + 1 - visible
+        } else {
+            offset
+        }
+    }
+
+    /// Get the configuration of this table, without its data or space (see `Layout`)
+    pub fn layout(&self) -> Layout {
+        Layout {
+            priorities: self.priorities.clone(),
+            align: self.align,
+            valign: self.valign,
+            distribution: self.distribution,
+            columns: self.columns.clone(),
+            surround: self.surround,
+            headers: self.headers,
+            border: self.border,
+            header_style: self.header_style,
+            ragged: self.ragged.clone(),
+            highlight: self.highlight,
+            measure: self.measure,
+            selection_mode: self.selection_mode,
+            cursor_style: self.cursor_style,
+            selected_style: self.selected_style,
+        }
+    }
+
+    /// Replace the configuration of this table, keeping its data and space (see `Layout`)
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.priorities = layout.priorities;
+        self.align = layout.align;
+        self.valign = layout.valign;
+        self.distribution = layout.distribution;
+        self.columns = layout.columns;
+        self.surround = layout.surround;
+        self.headers = layout.headers;
+        self.border = layout.border;
+        self.header_style = layout.header_style;
+        self.ragged = layout.ragged;
+        self.highlight = layout.highlight;
+        self.measure = layout.measure;
+        self.set_selection_mode(layout.selection_mode);
+        self.cursor_style = layout.cursor_style;
+        self.selected_style = layout.selected_style;
+    }
+
     /// Set the priorities for the columns.
     /// This allows you to control which columns to remove when space is limited.
     /// The higher the number of the column, the more important it is.
@@ -98,17 +839,355 @@ impl Table {
         self.priorities = priorities.to_vec();
     }
 
+    /// Set the priorities for the columns, referring to columns by their ids (see
+    /// `set_column_id`) rather than their positions.
+    ///
+    /// Columns that aren't mentioned get a priority of 0.
+    /// This will return an error if there is no column with one of the ids, or if the table has
+    /// ragged rows that aren't allowed (see `set_ragged`).
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["First name", "Surname", "Telephone"],
+    ///     vec!["John",       "Smith",   "04529834125"],
+    /// ];
+    /// let mut table = Table::new(data, 24);
+    /// table.set_column_id(0, "first");
+    /// table.set_column_id(2, "phone");
+    /// table.set_named_priorities(&[("first", 2), ("phone", 1)]).unwrap();
+    /// assert!(table.set_named_priorities(&[("email", 1)]).is_err());
+    /// ```
+    pub fn set_named_priorities(&mut self, priorities: &[(&str, usize)]) -> Result<()> {
+        let mut result = vec![0; self.column_count()?];
+        for (id, priority) in priorities {
+            let column = self
+                .column_index(id)
+                .ok_or(Error::UnknownColumn { id: id.to_string() })?;
+            // Ids can be given to columns past the end of the table, which have no priority
+            if let Some(slot) = result.get_mut(column) {
+                *slot = *priority;
+            }
+        }
+        self.priorities = result;
+        Ok(())
+    }
+
+    /// Give a column an id, so that it can be referred to by name instead of by position.
+    ///
+    /// The id stays with the column when columns are inserted, removed or moved.
+    pub fn set_column_id<T: Into<String>>(&mut self, column: usize, id: T) {
+        self.column_mut(column).id = Some(id.into());
+    }
+
+    /// Find the position of the column with this id (see `set_column_id`)
+    pub fn column_index(&self, id: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|s| s.id.as_deref() == Some(id))
+    }
+
     /// Set the alignment of each cell.
+    ///
+    /// This acts as the default for columns that don't have their own alignment set with
+    /// `set_column_alignment`.
     pub fn set_alignment(&mut self, align: Align) {
         self.align = align;
     }
 
+    /// Set the alignment of the cells in a specific column, overriding the table-wide alignment.
+    ///
+    /// The alignment stays with its column, even when other columns are removed due to limited
+    /// space.
+    /// Example:
+    /// ```
+    /// use alinio::table::{Align, Table};
+    /// let data = vec![
+    ///     vec!["Name",      "Size"],
+    ///     vec!["notes.txt", "2 KiB"],
+    ///     vec!["movie.mp4", "700 MiB"],
+    /// ];
+    /// let mut table = Table::new(data, 20);
+    /// // Right align the size column, everything else stays left aligned
+    /// table.set_column_alignment(1, Align::Right);
+    /// println!("{}\n---", table.render().unwrap().join("\n"))
+    /// ```
+    pub fn set_column_alignment(&mut self, column: usize, align: Align) {
+        self.column_mut(column).align = Some(align);
+    }
+
+    /// Clear the alignment of a specific column, so that it uses the table-wide alignment again.
+    pub fn reset_column_alignment(&mut self, column: usize) {
+        self.column_mut(column).align = None;
+    }
+
+    /// Set what happens to a column when there isn't enough space to fit the table.
+    ///
+    /// By default, columns are removed entirely, starting with the least important column (see
+    /// `set_priorities`). Columns that truncate are shrunk first, starting with the least important,
+    /// and columns are only removed if the table still doesn't fit once every column that
+    /// truncates is down to a single character.
+    /// Example:
+    /// ```
+    /// use alinio::table::{Overflow, Table};
+    /// let data = vec![
+    ///     vec!["Title",              "Artist"],
+    ///     vec!["Once in a Lifetime", "Talking Heads"],
+    /// ];
+    /// let mut table = Table::new(data, 27);
+    /// table.set_column_overflow(0, Overflow::Truncate);
+    /// // The title column is shrunk to fit, rather than being removed
+    /// assert_eq!(table.render().unwrap()[1], "Once in a Li… Talking Heads");
+    /// ```
+    pub fn set_column_overflow(&mut self, column: usize, overflow: Overflow) {
+        self.column_mut(column).overflow = overflow;
+    }
+
+    /// Word wrap the cells in a column, so that the column is never wider than `max_width`.
+    ///
+    /// Rows with wrapped cells will span multiple lines, and the other cells in the row are
+    /// padded according to the vertical alignment (see `set_vertical_alignment`).
+    /// Columns that wrap are shrunk when space is limited, in the same way as columns that
+    /// truncate (see `set_column_overflow`).
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Name", "Description"],
+    ///     vec!["ls",   "list directory contents"],
+    /// ];
+    /// let mut table = Table::new(data, 20);
+    /// table.set_column_wrap(1, 15);
+    /// assert_eq!(
+    ///     table.render().unwrap(),
+    ///     vec![
+    ///         "Name Description    ",
+    ///         "ls   list directory ",
+    ///         "     contents       ",
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_column_wrap(&mut self, column: usize, max_width: usize) {
+        self.column_mut(column).wrap = Some(max_width);
+    }
+
+    /// Stop word wrapping the cells in a column.
+    pub fn reset_column_wrap(&mut self, column: usize) {
+        self.column_mut(column).wrap = None;
+    }
+
+    /// Set the vertical alignment of cells in rows that span multiple lines.
+    pub fn set_vertical_alignment(&mut self, valign: VerticalAlign) {
+        self.valign = valign;
+    }
+
+    /// Set the style of the cells in a column.
+    ///
+    /// Styles are rendered to ANSI escape sequences, which don't take up any space.
+    /// When styles overlap, the most specific style wins, so cell styles take precedence over row
+    /// styles, which take precedence over the header style, which takes precedence over column
+    /// styles.
+    /// Example:
+    /// ```
+    /// use alinio::style::{Color, Style};
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Name",      "Size"],
+    ///     vec!["notes.txt", "2 KiB"],
+    /// ];
+    /// let mut table = Table::new(data, 20);
+    /// table.set_header_rows(1);
+    /// table.set_header_style(Style::new().bold());
+    /// table.set_column_style(1, Style::new().fg(Color::Green));
+    /// println!("{}\n---", table.render().unwrap().join("\n"))
+    /// ```
+    pub fn set_column_style(&mut self, column: usize, style: Style) {
+        self.column_mut(column).style = style;
+    }
+
+    /// Set the style of the header rows (see `set_header_rows`).
+    pub fn set_header_style(&mut self, style: Style) {
+        self.header_style = style;
+    }
+
+    /// Set the style of the cells in a row.
+    pub fn set_row_style(&mut self, row: usize, style: Style) {
+        self.row_styles.insert(row, style);
+    }
+
+    /// Set the style of an individual cell.
+    pub fn set_cell_style(&mut self, row: usize, column: usize, style: Style) {
+        self.cell_styles.insert((row, column), style);
+    }
+
+    /// Remove every row and cell style from the table.
+    pub fn clear_styles(&mut self) {
+        self.row_styles.clear();
+        self.cell_styles.clear();
+    }
+
+    /// Constrain the width of a column.
+    ///
+    /// Cells that are wider than their column are cut off, using the overflow behaviour of the
+    /// column (see `set_column_overflow`), or cut off at the end for columns that are dropped.
+    /// When space is limited, columns are shrunk down to their minimum width before any columns
+    /// are removed. Columns with a `Width::Min` constraint can shrink to that width, columns that
+    /// fill, truncate or wrap can shrink to a single character, and other columns don't shrink.
+    /// Example:
+    /// ```
+    /// use alinio::table::{Table, Width};
+    /// let data = vec![
+    ///     vec!["Name",      "Description"],
+    ///     vec!["notes.txt", "Some notes from the meeting"],
+    /// ];
+    /// let mut table = Table::new(data, 30);
+    /// table.set_column_width(0, Width::Fixed(6));
+    /// table.set_column_width(1, Width::Fill);
+    /// assert_eq!(
+    ///     table.render().unwrap(),
+    ///     vec![
+    ///         "Name   Description            ",
+    ///         "notes… Some notes from the me…",
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_column_width(&mut self, column: usize, width: Width) {
+        self.column_mut(column).width = width;
+    }
+
+    /// Set where any space that is left over once every column fits goes.
+    ///
+    /// By default, the space goes in the gutters between the columns. The other modes give the
+    /// space to the columns themselves, keeping each gutter a single character wide.
+    /// Only columns that can grow get the space (see `set_column_width`), and if any columns are
+    /// set to `Width::Fill`, only those columns get the space.
+    /// Example:
+    /// ```
+    /// use alinio::table::{Distribution, Table};
+    /// let data = vec![
+    ///     vec!["Name",      "Size"],
+    ///     vec!["notes.txt", "2 KiB"],
+    /// ];
+    /// let mut table = Table::new(data, 20);
+    /// table.set_distribution(Distribution::Even);
+    /// assert_eq!(
+    ///     table.render().unwrap(),
+    ///     vec![
+    ///         "Name         Size   ",
+    ///         "notes.txt    2 KiB  ",
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_distribution(&mut self, distribution: Distribution) {
+        self.distribution = distribution;
+    }
+
+    /// Set the weight of a column, for use with `Distribution::Weighted`.
+    ///
+    /// A column with a weight of 2 gets twice as much of the left over space as a column with a
+    /// weight of 1, which is the default.
+    pub fn set_column_weight(&mut self, column: usize, weight: usize) {
+        self.column_mut(column).weight = Some(weight);
+    }
+
+    /// Set what happens when rows in the table have different numbers of cells.
+    ///
+    /// By default, rendering fails with `Error::RaggedRow` when a row is shorter than the first
+    /// row, but irregular data (such as a hand-edited CSV file) can be padded or truncated instead.
+    /// Example:
+    /// ```
+    /// use alinio::table::{Ragged, Table};
+    /// let data = vec![
+    ///     vec!["Name", "Size", "Owner"],
+    ///     vec!["notes.txt", "2 KiB"],
+    /// ];
+    /// let mut table = Table::new(data, 22);
+    /// assert!(table.render().is_err());
+    /// table.set_ragged(Ragged::Pad("-".to_string()));
+    /// assert_eq!(
+    ///     table.render().unwrap(),
+    ///     vec![
+    ///         "Name       Size  Owner",
+    ///         "notes.txt  2 KiB -    ",
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_ragged(&mut self, ragged: Ragged) {
+        self.ragged = ragged;
+    }
+
+    /// Get the settings of a column, creating default settings if they don't exist yet
+    fn column_mut(&mut self, column: usize) -> &mut ColumnSettings {
+        if self.columns.len() <= column {
+            self.columns
+                .resize_with(column + 1, ColumnSettings::default);
+        }
+        &mut self.columns[column]
+    }
+
+    /// Move the row and cell styles, the cursor and the selection to follow their rows,
+    /// discarding them if their row is gone
+    fn remap_rows(&mut self, map: impl Fn(usize) -> Option<usize>) {
+        self.cursor = self.cursor.and_then(&map);
+        self.selected = self.selected.iter().filter_map(|r| map(*r)).collect();
+        self.row_styles = self
+            .row_styles
+            .drain()
+            .filter_map(|(r, style)| Some((map(r)?, style)))
+            .collect();
+        self.cell_styles = self
+            .cell_styles
+            .drain()
+            .filter_map(|((r, c), style)| Some(((map(r)?, c), style)))
+            .collect();
+    }
+
+    /// Move the cell styles to follow their columns, discarding them if their column is gone
+    fn remap_columns(&mut self, map: impl Fn(usize) -> Option<usize>) {
+        self.cell_styles = self
+            .cell_styles
+            .drain()
+            .filter_map(|((r, c), style)| Some(((r, map(c)?), style)))
+            .collect();
+    }
+
     /// When `surround` is true, padding will be applied to the sides of the table.
     /// When `surround` is false, the table will take the full width.
+    ///
+    /// This has no effect when the table has a visible border (see `set_border`).
     pub fn set_surround(&mut self, surround: bool) {
         self.surround = surround;
     }
 
+    /// Mark the first `headers` rows of the table as header rows.
+    ///
+    /// Header rows are always rendered at the top of the table, even when using
+    /// `render_partial` to scroll through the rest of the table.
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let data = vec![
+    ///     vec!["Title",              "Artist"],
+    ///     vec!["Once in a Lifetime", "Talking Heads"],
+    ///     vec!["Heroes",             "David Bowie"],
+    /// ];
+    /// let mut table = Table::new(data, 40);
+    /// table.set_header_rows(1);
+    /// // This will render the header row, followed by the "Heroes" row
+    /// println!("{}\n---", table.render_partial(1).unwrap().join("\n"))
+    /// ```
+    pub fn set_header_rows(&mut self, headers: usize) {
+        self.headers = headers;
+    }
+
+    /// Set the borders to draw around and within the table.
+    ///
+    /// The border characters are taken into account when working out which columns fit into the
+    /// space of the table. Any space left over is given to the columns.
+    pub fn set_border(&mut self, border: Border) {
+        self.border = border;
+    }
+
     /// Set the space between each cell.
     /// Use this if your terminal size updates.
     pub fn set_space(&mut self, space: usize) {
@@ -117,86 +1196,480 @@ impl Table {
 
     /// Render this table to rows of strings.
     ///
-    /// This will return `None` if there is not enough space to fit the table.
-    pub fn render(&self) -> Option<Vec<String>> {
+    /// This will return an error if the table is in an invalid format.
+    pub fn render(&self) -> Result<Vec<String>> {
         self.render_partial(0)
     }
 
     /// Only renders rows after `offset` row. This is particularly useful if you have a table
     /// that you wish to fit into a terminal with a height shorter than the table.
     ///
-    /// This will return `None` if there is not enough space to fit the table, or if the offset is
-    /// out of bounds
-    pub fn render_partial(&self, offset: usize) -> Option<Vec<String>> {
-        // Return nothing if there is no data
-        if self.data.len().saturating_sub(offset) == 0 {
-            return Some(vec![]);
-        }
-        // Create copy of data
-        let mut data = self.data.clone();
-        // Reform into columns
-        let mut columns = vec![];
-        for column in 0..data[0].len() {
-            let mut this = vec![];
-            for row in &data {
-                // Returns None if table is in an invalid format
-                this.push(row.get(column)?)
-            }
-            columns.push(this);
+    /// Header rows (see `set_header_rows`) are always rendered, and `offset` only applies to
+    /// the rows after them.
+    ///
+    /// This will return an error if the table is in an invalid format.
+    /// Offsets past the end of the table will only render the header rows.
+    pub fn render_partial(&self, offset: usize) -> Result<Vec<String>> {
+        self.render_range(offset, usize::MAX)
+    }
+
+    /// Only renders at most `height` rows, starting after `offset` row. This is useful for
+    /// rendering a viewport of a very large table, as only the rows that are visible get aligned
+    /// and padded.
+    ///
+    /// Header rows (see `set_header_rows`) and border lines (see `set_border`) are always
+    /// rendered and count towards `height`, and `offset` only applies to the rows after them.
+    /// Column widths are worked out using every row in the table, so they stay the same
+    /// regardless of which rows are being rendered.
+    ///
+    /// This will return an error if the table is in an invalid format.
+    /// Example:
+    /// ```
+    /// use alinio::table::Table;
+    /// let mut data = vec![vec!["Name".to_string(), "Size".to_string()]];
+    /// for i in 0..100_000 {
+    ///     data.push(vec![format!("file{}.txt", i), format!("{} KiB", i % 64)]);
+    /// }
+    /// let mut table = Table::new(data, 40);
+    /// table.set_header_rows(1);
+    /// // Render the header, followed by the 39 rows after the 500th row
+    /// let view = table.render_range(500, 40).unwrap();
+    /// assert_eq!(view.len(), 40);
+    /// ```
+    pub fn render_range(&self, offset: usize, height: usize) -> Result<Vec<String>> {
+        // Work out how many lines the borders will take up
+        let border = self.border.is_visible();
+        let frame = border && self.border.frame;
+        let separator = border && self.border.header && self.headers > 0;
+        let height = height.saturating_sub(2 * frame as usize + separator as usize);
+        // Work out which rows to render, header rows are always rendered
+        let headers = self.headers.min(self.data.len()).min(height);
+        let body = self.matches();
+        let start = offset.min(body.len());
+        let end = start.saturating_add(height - headers).min(body.len());
+        let rows = (0..headers).chain(body[start..end].iter().copied());
+        // Return nothing if there are no rows to render
+        if rows.clone().next().is_none() {
+            return Ok(vec![]);
+        }
+        // Work out which columns fit, and how wide they are
+        let (keep, limits) = self.fit_columns(&body, self.space)?;
+        // Correctly align each item within said columns and format them
+        let mut result = vec![];
+        if frame && !keep.is_empty() {
+            result.push(self.border.top(&limits));
+        }
+        let mut remaining = height;
+        'rows: for r in rows {
+            // Split each cell into lines
+            let mut cells = vec![];
+            for (column, limit) in keep.iter().zip(&limits) {
+                // Wrap cell if the column wraps
+                if self.column_wrap(*column).is_some() {
+                    cells.push(align::wrap(self.text(r, *column), *limit));
+                    continue;
+                }
+                // Truncate cell if the column has been shrunk
+                let at = match self.column_overflow(*column) {
+                    Overflow::Drop | Overflow::Truncate => Truncate::End,
+                    Overflow::TruncateStart => Truncate::Start,
+                    Overflow::TruncateMiddle => Truncate::Middle,
+                };
+                let text = self.text(r, *column);
+                cells.push(vec![align::truncate(text, *limit, ELLIPSIS, at)]);
+            }
+            // Render each line of this row
+            let lines = cells.iter().map(|c| c.len()).max().unwrap_or(1);
+            for line in 0..lines {
+                // Stop once the height has been filled
+                if remaining == 0 {
+                    break 'rows;
+                }
+                remaining -= 1;
+                let mut this = vec![];
+                for ((column, limit), cell) in keep.iter().zip(&limits).zip(&cells) {
+                    let style = self.cell_style(r, *column);
+                    // Work out which line of the cell to show on this line
+                    let first = match self.valign {
+                        VerticalAlign::Top => 0,
+                        VerticalAlign::Middle => (lines - cell.len()) / 2,
+                        VerticalAlign::Bottom => lines - cell.len(),
+                    };
+                    let text = line
+                        .checked_sub(first)
+                        .and_then(|l| cell.get(l))
+                        .map_or("", |t| t.as_str());
+                    let text = self.highlight(text, style);
+                    // Align cell
+                    let cell = match self.column_alignment(*column) {
+                        Align::Left => align::left(&text, *limit),
+                        Align::Right => align::right(&text, *limit),
+                        Align::Center => align::center(&text, *limit),
+                    };
+                    this.push(style.paint(&cell?));
+                }
+                // Join the cells together with the border if there is one
+                let joined = if border && !keep.is_empty() {
+                    self.border.row(&this)
+                } else {
+                    // Get parts as a vector of &str (for use in align functions)
+                    let parts = this.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+                    // Do alignment
+                    if self.surround {
+                        align::around(parts.as_slice(), self.space)
+                    } else {
+                        align::between(parts.as_slice(), self.space)
+                    }?
+                };
+                // Draw the cursor and selection across the whole line
+                result.push(paint_line(self.selection_style(r), &joined));
+            }
+            // Separate header rows from the rest of the table, once all of them are rendered
+            if separator && !keep.is_empty() && r + 1 == self.header_rows() {
+                result.push(self.border.middle(&limits));
+            }
+        }
+        if frame && !keep.is_empty() {
+            result.push(self.border.bottom(&limits));
+        }
+        Ok(result)
+    }
+
+    /// Work out how many columns the table has, depending on how ragged rows are handled
+    ///
+    /// This will return an error if a row is too short and ragged rows aren't allowed
+    pub(crate) fn column_count(&self) -> Result<usize> {
+        let lengths = self.data.iter().map(|row| row.len());
+        match self.ragged {
+            Ragged::Error => {
+                let expected = self.data.first().map_or(0, |row| row.len());
+                for (row, found) in lengths.enumerate() {
+                    if found < expected {
+                        return Err(Error::RaggedRow {
+                            row,
+                            expected,
+                            found,
+                        });
+                    }
+                }
+                Ok(expected)
+            }
+            Ragged::Pad(_) => Ok(lengths.max().unwrap_or(0)),
+            Ragged::Truncate => Ok(self.data.first().map_or(0, |row| row.len())),
         }
+    }
+
+    /// Returns true if a row matches the filter and the query
+    fn is_match(&self, row: &[String]) -> bool {
+        if let Some(filter) = &self.filter {
+            if !filter(row) {
+                return false;
+            }
+        }
+        match &self.query {
+            Some(query) => row.iter().any(|c| find(&align::strip(c), query).is_some()),
+            None => true,
+        }
+    }
+
+    /// Highlight the parts of the text that match the query, going back to the style of the cell
+    /// after each match
+    fn highlight(&self, txt: &str, style: Style) -> String {
+        let query = match &self.query {
+            Some(query) if !self.highlight.is_plain() && !txt.contains('\x1b') => query,
+            _ => return txt.to_string(),
+        };
+        let mut result = "".to_string();
+        let mut rest = txt;
+        while let Some((start, end)) = find(rest, query) {
+            result.push_str(&rest[..start]);
+            result.push_str(&self.highlight.paint(&rest[start..end]));
+            result.push_str(&style.escape());
+            rest = &rest[end..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Get the text of a cell, using the placeholder if the cell is missing from a padded row, or
+    /// empty text if it is missing from a truncated row
+    pub(crate) fn text(&self, row: usize, column: usize) -> &String {
+        static EMPTY: String = String::new();
+        match &self.ragged {
+            Ragged::Pad(placeholder) => self.data[row].get(column).unwrap_or(placeholder),
+            Ragged::Truncate => self.data[row].get(column).unwrap_or(&EMPTY),
+            _ => &self.data[row][column],
+        }
+    }
+
+    /// Work out which columns fit into `space`, and how wide each of them is, given the rows after
+    /// the header rows that are being rendered
+    ///
+    /// This will return an error if the table is in an invalid format.
+    pub(crate) fn fit_columns(
+        &self,
+        body: &[usize],
+        space: usize,
+    ) -> Result<(Vec<usize>, Vec<usize>)> {
+        // Work out which rows to measure the columns with
+        let measured: Vec<usize> = match self.measure {
+            Measure::AllRows => (0..self.data.len()).collect(),
+            Measure::MatchingRows => (0..self.headers.min(self.data.len()))
+                .chain(body.iter().copied())
+                .collect(),
+        };
         // For each column in this table, work out the maximum space required
         let mut limits = vec![];
-        for column in &columns {
-            limits.push(find_longest(column));
+        for column in 0..self.column_count()? {
+            let this: Vec<&String> = measured.iter().map(|r| self.text(*r, column)).collect();
+            // Wrapped columns are never wider than their maximum width
+            let longest = find_longest(&this);
+            let longest = match self.column_wrap(column) {
+                Some(max) => longest.min(max),
+                None => longest,
+            };
+            // Apply width constraints
+            limits.push(match self.column_width(column) {
+                Width::Auto | Width::Fill => longest,
+                Width::Fixed(width) => width,
+                Width::Min(min) => longest.max(min),
+                Width::Max(max) => longest.min(max),
+                // Percentages are worked out once it is known which columns are kept
+                Width::Percent(_) => 0,
+            });
         }
+        // Keep track of which columns are still in the table
+        let mut keep: Vec<usize> = (0..limits.len()).collect();
+        let mut natural = limits.clone();
         // Strip columns until it fits
+        // Ignore priorities for columns past the end of the table
         let mut pri = self.priorities.clone();
-        let mut pad_places = if self.surround {
-            columns.len() + 1
-        } else {
-            columns.len().saturating_sub(1)
-        };
-        let mut column_count = columns.len().saturating_sub(1);
-        while limits.iter().sum::<usize>() + pad_places > self.space {
+        pri.truncate(keep.len());
+        let mut column_count = keep.len().saturating_sub(1);
+        loop {
+            // Work out percentages from the space left over after padding the kept columns
+            let available = space.saturating_sub(self.padding(keep.len()));
+            for (c, column) in keep.iter().enumerate() {
+                if let Width::Percent(percent) = self.column_width(*column) {
+                    natural[c] = available * percent.min(100) / 100;
+                }
+            }
+            // Work out how much space needs to be freed up
+            let mut excess =
+                (natural.iter().sum::<usize>() + self.padding(keep.len())).saturating_sub(space);
+            // Shrink columns down to their minimum width, starting with the least important
+            limits = natural.clone();
+            let mut order: Vec<usize> = (0..keep.len()).rev().collect();
+            order.sort_by_key(|c| pri.get(*c).copied().unwrap_or(0));
+            for c in order {
+                let shrink = excess.min(limits[c] - self.column_minimum(keep[c], limits[c]));
+                limits[c] -= shrink;
+                excess -= shrink;
+            }
+            // Stop once the table fits
+            if excess == 0 {
+                break;
+            }
             // Work out which column to remove
             let rm = pri.iter().min().unwrap_or(&0);
             let rm = pri.iter().position(|x| x == rm).unwrap_or(column_count);
-            // Remove from data
-            for row in data.iter_mut().skip(offset) {
-                row.remove(rm);
-            }
-            // Remove from limits
-            limits.remove(rm);
+            // Remove from the kept columns and limits
+            keep.remove(rm);
+            natural.remove(rm);
             // Remove from priority
             if !pri.is_empty() {
                 pri.remove(rm);
             }
             // Decrement counters
-            pad_places = pad_places.saturating_sub(1);
             column_count = column_count.saturating_sub(1);
         }
-        // Correctly align each item within said columns and format them
-        let mut result = vec![];
-        for row in data.iter().skip(offset) {
-            let mut this = vec![];
-            for (column, limit) in row.iter().zip(&limits) {
-                // Align cell
-                let cell = match self.align {
-                    Align::Left => align::left(column, *limit),
-                    Align::Right => align::right(column, *limit),
-                    Align::Center => align::center(column, *limit),
-                };
-                this.push(cell.unwrap());
-            }
-            // Get parts as a vector of &str (for use in align functions)
-            let parts = this.iter().map(|x| x.as_str()).collect::<Vec<_>>();
-            // Do alignment
-            result.push(if self.surround {
-                align::around(parts.as_slice(), self.space)
+        // Give any left over space to the columns that fill, or to every column that can grow
+        // when the space doesn't go in the gutters
+        let left_over = space - limits.iter().sum::<usize>() - self.padding(keep.len());
+        let fill: Vec<usize> = (0..keep.len())
+            .filter(|c| self.column_width(keep[*c]) == Width::Fill)
+            .collect();
+        let grow: Vec<usize> = (0..keep.len())
+            .filter(|c| {
+                let width = self.column_width(keep[*c]);
+                matches!(width, Width::Auto | Width::Min(_) | Width::Fill)
+            })
+            .collect();
+        let targets = if !fill.is_empty() {
+            fill
+        } else if self.border.is_visible() || self.distribution != Distribution::Gutters {
+            if grow.is_empty() {
+                (0..keep.len()).collect()
             } else {
-                align::between(parts.as_slice(), self.space)
-            }?);
+                grow
+            }
+        } else {
+            vec![]
+        };
+        let weights: Vec<usize> = targets
+            .iter()
+            .map(|c| match self.distribution {
+                Distribution::Gutters | Distribution::Even => 1,
+                Distribution::Proportional => limits[*c],
+                Distribution::Weighted => self.column_weight(keep[*c]),
+            })
+            .collect();
+        distribute(&mut limits, &targets, &weights, left_over);
+        Ok((keep, limits))
+    }
+
+    /// Work out how much padding is required between and around a number of columns
+    fn padding(&self, columns: usize) -> usize {
+        if columns == 0 {
+            0
+        } else if self.border.is_visible() {
+            self.border.padding(columns)
+        } else if self.surround {
+            columns + 1
+        } else {
+            columns - 1
+        }
+    }
+
+    /// Work out the alignment of a column
+    pub(crate) fn column_alignment(&self, column: usize) -> Align {
+        self.columns
+            .get(column)
+            .and_then(|s| s.align)
+            .unwrap_or(self.align)
+    }
+
+    /// Work out the style of a row from the cursor and the selection
+    fn selection_style(&self, row: usize) -> Style {
+        let mut style = Style::new();
+        if self.is_selected(row) {
+            style = style.patch(self.selected_style);
+        }
+        if self.cursor == Some(row) {
+            style = style.patch(self.cursor_style);
+        }
+        style
+    }
+
+    /// Work out the style of a cell, combining the column, header, row and cell styles
+    fn cell_style(&self, row: usize, column: usize) -> Style {
+        let mut style = self
+            .columns
+            .get(column)
+            .map(|s| s.style)
+            .unwrap_or_default();
+        if row < self.headers {
+            style = style.patch(self.header_style);
+        }
+        if let Some(row) = self.row_styles.get(&row) {
+            style = style.patch(*row);
+        }
+        if let Some(cell) = self.cell_styles.get(&(row, column)) {
+            style = style.patch(*cell);
+        }
+        style
+    }
+
+    /// Work out the width constraint of a column
+    fn column_width(&self, column: usize) -> Width {
+        self.columns
+            .get(column)
+            .map(|s| s.width)
+            .unwrap_or_default()
+    }
+
+    /// Work out how narrow a column can be shrunk, given its width before shrinking
+    fn column_minimum(&self, column: usize, width: usize) -> usize {
+        let shrinkable =
+            self.column_overflow(column) != Overflow::Drop || self.column_wrap(column).is_some();
+        match self.column_width(column) {
+            Width::Fixed(_) => width,
+            Width::Min(min) => min.min(width),
+            Width::Percent(_) | Width::Fill => width.min(1),
+            _ if shrinkable => width.min(1),
+            _ => width,
         }
-        Some(result)
+    }
+
+    /// Work out the weight of a column
+    fn column_weight(&self, column: usize) -> usize {
+        self.columns.get(column).and_then(|s| s.weight).unwrap_or(1)
+    }
+
+    /// Work out the maximum width of a column, if it wraps
+    fn column_wrap(&self, column: usize) -> Option<usize> {
+        self.columns.get(column).and_then(|s| s.wrap)
+    }
+
+    /// Work out the overflow behaviour of a column
+    fn column_overflow(&self, column: usize) -> Overflow {
+        self.columns
+            .get(column)
+            .map(|s| s.overflow)
+            .unwrap_or_default()
+    }
+}
+
+/// Apply a style to a whole line, going back to the style after every reset within the line
+fn paint_line(style: Style, line: &str) -> String {
+    if style.is_plain() {
+        return line.to_string();
+    }
+    let escape = style.escape();
+    let line = line.replace("\x1b[0m", &format!("\x1b[0m{}", escape));
+    style.paint(&line)
+}
+
+/// Find the first part of the text that matches the query, ignoring case, as a range of bytes
+fn find(txt: &str, query: &str) -> Option<(usize, usize)> {
+    for (start, _) in txt.char_indices() {
+        let mut chars = txt[start..].chars();
+        let mut end = start;
+        let found = query.chars().all(|q| match chars.next() {
+            Some(c) if c.to_lowercase().eq(q.to_lowercase()) => {
+                end += c.len_utf8();
+                true
+            }
+            _ => false,
+        });
+        if found {
+            return Some((start, end));
+        }
+    }
+    None
+}
+
+/// Work out where an index ends up after the item at `from` is moved to `to`
+fn moved(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        return to;
+    }
+    let index = if index > from { index - 1 } else { index };
+    if index >= to {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Share out space between some of the columns in proportion to their weights, giving any
+/// remainder to the first columns
+fn distribute(limits: &mut [usize], columns: &[usize], weights: &[usize], space: usize) {
+    if columns.is_empty() {
+        return;
+    }
+    // Share the space evenly if none of the columns have any weight
+    let total: usize = weights.iter().sum();
+    if total == 0 {
+        return distribute(limits, columns, &vec![1; columns.len()], space);
+    }
+    let mut given = 0;
+    for (c, weight) in columns.iter().zip(weights) {
+        let share = space * weight / total;
+        limits[*c] += share;
+        given += share;
+    }
+    for c in columns.iter().take(space - given) {
+        limits[*c] += 1;
     }
 }